@@ -55,6 +55,14 @@ pub(super) async fn start() -> std::io::Result<()> {
 
     let locale_dict_arc = load_translations(&config);
 
+    if let Some(threshold) = config.get_slow_query_threshold() {
+        search::slow_query::set_threshold(threshold);
+    }
+
+    if let Some(capacity) = config.get_reading_cache_size() {
+        search::word::cache::set_capacity(capacity);
+    }
+
     #[cfg(feature = "sentry_error")]
     if let Some(ref sentry_config) = config.sentry {
         use std::mem::ManuallyDrop;
@@ -157,10 +165,18 @@ pub(super) async fn start() -> std::io::Result<()> {
                                 actixweb::post().to(api::search::sentence::sentence_search),
                             ),
                     )
+                    .route(
+                        "/word/{seq}",
+                        actixweb::get().to(api::search::word::word_by_seq),
+                    )
                     .route(
                         "/kanji/by_radical",
                         actixweb::post().to(api::radical::kanji_by_radicals),
                     )
+                    .route(
+                        "/kanji/annotate",
+                        actixweb::post().to(api::annotate::annotate_word),
+                    )
                     .route(
                         "/radical/search",
                         actixweb::post().to(api::radical::search::search_radical),
@@ -170,6 +186,8 @@ pub(super) async fn start() -> std::io::Result<()> {
                         actixweb::post().to(api::completions::suggestion_ep),
                     )
                     .route("/img_scan", actixweb::post().to(api::img::scan_ep))
+                    .route("/convert", actixweb::post().to(api::convert::convert))
+                    .route("/split", actixweb::post().to(api::split::split))
                     .route("/news/short", actixweb::post().to(api::news::short::news))
                     .route(
                         "/news/detailed",
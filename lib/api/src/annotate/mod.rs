@@ -0,0 +1,20 @@
+use actix_web::web::Json;
+use error::api_error::RestError;
+use search::kanji::KanjiAnnotation;
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize)]
+pub struct Request {
+    pub word: String,
+}
+
+#[derive(Serialize)]
+pub struct Response {
+    pub kanji: Vec<KanjiAnnotation>,
+}
+
+/// Get the readings and meanings of all kanji within a word endpoint
+pub async fn annotate_word(payload: Json<Request>) -> Result<Json<Response>, RestError> {
+    let kanji = search::kanji::annotate_word(&payload.word);
+    Ok(Json(Response { kanji }))
+}
@@ -51,6 +51,10 @@ pub async fn suggestion_ep(
 
 /// Returns best matching suggestions for the given query
 async fn get_suggestions(query: Query) -> Result<Response, RestError> {
+    if query.only_symbols {
+        return Ok(Response::default());
+    }
+
     match query.type_ {
         QueryType::Sentences | QueryType::Words => {
             if let Some(kanji_reading) = as_kanji_reading(&query) {
@@ -91,7 +95,7 @@ fn as_kanji_reading(query: &Query) -> Option<models::kanji::Reading> {
             let first = query_str.next()?;
             let second = query_str.next()?;
 
-            if first.is_kanji() && second == ' ' {
+            if (first.is_kanji() || first.is_rare_kanji()) && second == ' ' {
                 Some(models::kanji::Reading {
                     reading: String::new(),
                     literal: first,
@@ -146,8 +150,5 @@ fn word_pair_order(a: &WordPair, b: &WordPair, query: &str) -> Ordering {
 
 /// Returns an equivalent katakana query
 fn get_katakana_query(query: &Query) -> Query {
-    Query {
-        query: romaji::RomajiExt::to_katakana(query.query.as_str()),
-        ..query.clone()
-    }
+    query.with_query(romaji::RomajiExt::to_katakana(query.query.as_str()))
 }
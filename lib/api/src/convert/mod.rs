@@ -0,0 +1,72 @@
+use std::str::FromStr;
+
+use actix_web::web::Json;
+use error::{api_error::RestError, Error};
+use japanese::JapaneseExt;
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize)]
+pub struct Request {
+    pub text: String,
+    pub target: String,
+}
+
+#[derive(Serialize)]
+pub struct Response {
+    pub text: String,
+}
+
+/// The script to convert the text to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Target {
+    Hiragana,
+    Katakana,
+    Romaji,
+}
+
+impl FromStr for Target {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.trim().to_lowercase().as_str() {
+            "hiragana" => Self::Hiragana,
+            "katakana" => Self::Katakana,
+            "romaji" => Self::Romaji,
+            _ => return Err(Error::ParseError),
+        })
+    }
+}
+
+/// Convert text between romaji, hiragana and katakana endpoint
+pub async fn convert(payload: Json<Request>) -> Result<Json<Response>, RestError> {
+    let target = Target::from_str(&payload.target)?;
+    let text = convert_text(&payload.text, target);
+    Ok(Json(Response { text }))
+}
+
+/// Converts `text` into the script of `target`
+fn convert_text(text: &str, target: Target) -> String {
+    match target {
+        Target::Hiragana => text.to_hiragana(),
+        Target::Katakana => text.to_katakana(),
+        Target::Romaji => text.to_romaji(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_convert_text() {
+        assert_eq!(convert_text("sushi", Target::Hiragana), "すし");
+        assert_eq!(convert_text("すし", Target::Katakana), "スシ");
+        assert_eq!(convert_text("すし", Target::Romaji), "sushi");
+    }
+
+    #[test]
+    fn test_parse_target() {
+        assert_eq!(Target::from_str("Katakana").unwrap(), Target::Katakana);
+        assert!(matches!(Target::from_str("kanji"), Err(Error::ParseError)));
+    }
+}
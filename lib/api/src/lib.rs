@@ -1,5 +1,8 @@
+pub mod annotate;
 pub mod completions;
+pub mod convert;
 pub mod img;
 pub mod news;
 pub mod radical;
 pub mod search;
+pub mod split;
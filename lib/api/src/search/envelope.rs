@@ -0,0 +1,90 @@
+use std::time::Duration;
+
+use serde::Serialize;
+
+/// Wraps a search API response with information about the search which produced it
+#[derive(Serialize)]
+pub struct Envelope<T: Serialize> {
+    /// The query the search was run with
+    query: String,
+    /// Time the search took in milliseconds
+    took_ms: u64,
+    source: ResultSource,
+    status: Status,
+    data: T,
+}
+
+/// Whether a search found anything. Searches without results still respond with 200, other
+/// status codes are only used for errors
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    Ok,
+    /// Nothing was found, `data` holds an empty result
+    Empty,
+}
+
+/// The kind of search which produced the results
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ResultSource {
+    /// Results were found for the query itself
+    Exact,
+    /// Results were found for a modified query, eg. a deinflected word
+    Fallback,
+    /// Nothing was found but a different query was suggested
+    Suggestion,
+}
+
+impl<T: Serialize> Envelope<T> {
+    #[inline]
+    pub fn new(query: String, took: Duration, source: ResultSource, data: T, empty: bool) -> Self {
+        let status = if empty { Status::Empty } else { Status::Ok };
+        Self {
+            query,
+            took_ms: took.as_millis() as u64,
+            source,
+            status,
+            data,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_envelope_fields() {
+        let envelope = Envelope::new(
+            "すし".to_string(),
+            Duration::from_millis(12),
+            ResultSource::Fallback,
+            vec![1, 2],
+            false,
+        );
+
+        let value = serde_json::to_value(&envelope).unwrap();
+        assert_eq!(value["query"], "すし");
+        assert_eq!(value["took_ms"], 12);
+        assert_eq!(value["source"], "fallback");
+        assert_eq!(value["status"], "ok");
+        assert_eq!(value["data"], serde_json::json!([1, 2]));
+    }
+
+    #[test]
+    fn test_empty_envelope() {
+        let data: Vec<u32> = vec![];
+        let envelope = Envelope::new(
+            "すし".to_string(),
+            Duration::from_millis(1),
+            ResultSource::Exact,
+            data,
+            true,
+        );
+
+        let value = serde_json::to_value(&envelope).unwrap();
+        assert_eq!(value["status"], "empty");
+        assert_eq!(value["data"], serde_json::json!([]));
+    }
+}
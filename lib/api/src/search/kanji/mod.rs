@@ -1,17 +1,21 @@
-use actix_web::web::{self, Json};
+use actix_web::web::{Data, Json};
+use config::Config;
 use search::query_parser::QueryType::Kanji;
 
 use self::response::Response;
 
-use super::{Result, SearchRequest};
+use super::{with_timeout, Result, SearchRequest};
 
 pub mod response;
 
 /// Do a kanji search via API
-pub async fn kanji_search(payload: Json<SearchRequest>) -> Result<Json<Response>> {
+pub async fn kanji_search(
+    payload: Json<SearchRequest>,
+    config: Data<Config>,
+) -> Result<Json<Response>> {
     let query = SearchRequest::parse(payload, Kanji)?;
-    let result = web::block(move || search::kanji::search(&query))
-        .await??
+    let result = with_timeout(&config, move || search::kanji::search(&query))
+        .await?
         .items;
     Ok(Json(result.into()))
 }
@@ -1,19 +1,31 @@
+pub mod envelope;
 pub mod kanji;
 pub mod name;
 pub mod sentence;
 pub mod word;
 
-use actix_web::web::Json;
-use error::api_error::RestError;
-use resources::parse::jmdict::languages::Language;
+use actix_web::{
+    rt::time::timeout,
+    web::{self, Json},
+};
+use config::Config;
+use error::{api_error::RestError, Error};
+use once_cell::sync::OnceCell;
+use resources::parse::{jmdict::languages::Language, jmnedict::name_type::NameType};
 use search::{
     query::{Query, UserSettings},
     query_parser::{QueryParser, QueryType},
+    search_order::NamedOrder,
 };
 use serde::Deserialize;
+use std::{str::FromStr, sync::Arc};
+use tokio::sync::Semaphore;
 
 pub type Result<T> = std::result::Result<T, RestError>;
 
+/// Limits the amount of concurrently running searches
+static SEARCH_PERMITS: OnceCell<Arc<Semaphore>> = OnceCell::new();
+
 /// An Search API payload
 #[derive(Deserialize)]
 pub struct SearchRequest {
@@ -25,6 +37,68 @@ pub struct SearchRequest {
 
     #[serde(default)]
     no_english: bool,
+
+    /// Include aligned furigana within word results
+    #[serde(default)]
+    include_furigana: bool,
+
+    /// Include the raw JMdict tags of senses within word results
+    #[serde(default)]
+    include_raw_tags: bool,
+
+    /// Include the estimated amount of example sentences of each word
+    #[serde(default)]
+    include_example_count: bool,
+
+    /// Max amount of kanji to return alongside words
+    #[serde(default)]
+    kanji_limit: Option<usize>,
+
+    /// Whether to load kanji alongside words at all
+    #[serde(default = "default_load_kanji_info")]
+    load_kanji_info: bool,
+
+    /// Only return names of this type, eg. `place`
+    #[serde(default)]
+    name_type: Option<String>,
+
+    /// Put the word consisting of the kanji itself to the top of kanji reading searches
+    #[serde(default)]
+    include_kanji_word: bool,
+
+    /// List kana written words with the searched reading after the compounds of kanji reading
+    /// searches
+    #[serde(default)]
+    include_kana_words: bool,
+
+    /// Find kana words with the same normalized reading if nothing else was found, eg. ラーメン for
+    /// らあめん
+    #[serde(default)]
+    normalize_kana: bool,
+
+    /// Don't match readings written in the other kana script, eg. こーひー for コーヒー
+    #[serde(default)]
+    strict_script: bool,
+
+    /// Find kana words spelled with or without a trailing ー, eg. コンピューター for コンピュータ
+    #[serde(default)]
+    ignore_trailing_long_mark: bool,
+
+    /// Rank compounds using non jōyō kanji lower within kanji reading searches
+    #[serde(default)]
+    prefer_joyo: bool,
+
+    /// Search for well-known alternate spellings of the query too, eg. 子ども for 子供
+    #[serde(default)]
+    expand_variants: bool,
+
+    /// Merge senses of found words having the same glosses into one sense
+    #[serde(default)]
+    merge_senses: bool,
+
+    /// Sort found words by this ordering instead of their relevance, eg. `frequency`
+    #[serde(default)]
+    order: Option<String>,
 }
 
 impl SearchRequest {
@@ -35,10 +109,174 @@ impl SearchRequest {
             ..UserSettings::default()
         };
 
-        let query = QueryParser::new(payload.query_str.clone(), q_type, settings, 0, 0, true)
+        let mut query = QueryParser::new(payload.query_str.clone(), q_type, settings, 0, 0, true)
             .parse()
             .ok_or(RestError::BadRequest)?;
+        query.kanji_info_limit = payload.kanji_limit;
+        query.skip_kanji_info = !payload.load_kanji_info;
+        query.include_kanji_word = payload.include_kanji_word;
+        query.include_kana_words = payload.include_kana_words;
+        query.normalize_kana = payload.normalize_kana;
+        query.strict_script = payload.strict_script;
+        query.ignore_trailing_long_mark = payload.ignore_trailing_long_mark;
+        query.prefer_joyo = payload.prefer_joyo;
+        query.expand_variants = payload.expand_variants;
+        query.merge_senses = payload.merge_senses;
+        query.name_type = payload
+            .name_type
+            .as_deref()
+            .map(NameType::from_str)
+            .transpose()
+            .map_err(|_| RestError::BadRequest)?;
+        query.order = payload
+            .order
+            .as_deref()
+            .map(NamedOrder::from_str)
+            .transpose()
+            .map_err(|_| RestError::BadRequest)?;
 
         Ok(query)
     }
 }
+
+#[inline]
+fn default_load_kanji_info() -> bool {
+    true
+}
+
+/// Treats searches failing with `NotFound` like searches without results, so only actual errors
+/// respond with a non 200 status. The status of each error is defined by [`RestError`]
+#[inline]
+pub(crate) fn or_empty<T: Default>(res: Result<T>) -> Result<T> {
+    match res {
+        Err(RestError::NotFound) => Ok(T::default()),
+        res => res,
+    }
+}
+
+/// Runs the blocking search `f` on the thread pool. At most `max_concurrent_searches` searches
+/// run at once, others wait for a free slot. Returns `Error::Timeout` if the search doesn't
+/// finish within the configured search timeout, including the time spent waiting
+pub(crate) async fn with_timeout<F, R>(config: &Config, f: F) -> Result<R>
+where
+    F: FnOnce() -> std::result::Result<R, Error> + Send + 'static,
+    R: Send + 'static,
+{
+    let permits = SEARCH_PERMITS
+        .get_or_init(|| Arc::new(Semaphore::new(config.get_max_concurrent_searches())))
+        .clone();
+
+    timeout(config.get_search_timeout(), with_permit(permits, f))
+        .await
+        .map_err(|_| Error::Timeout)?
+}
+
+/// Runs the blocking search `f` on the thread pool once one of `permits` is free. The permit is
+/// owned by the blocking task, so it's only released once `f` returns, even if the search timed
+/// out before
+async fn with_permit<F, R>(permits: Arc<Semaphore>, f: F) -> Result<R>
+where
+    F: FnOnce() -> std::result::Result<R, Error> + Send + 'static,
+    R: Send + 'static,
+{
+    let permit = permits
+        .acquire_owned()
+        .await
+        .map_err(|_| Error::Unexpected)?;
+
+    let res = web::block(move || {
+        let res = f();
+        drop(permit);
+        res
+    })
+    .await??;
+
+    Ok(res)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use actix_web::{http::StatusCode, ResponseError};
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        thread,
+        time::Duration,
+    };
+
+    fn parse(payload: &str) -> Result<Query> {
+        let payload: SearchRequest = serde_json::from_str(payload).unwrap();
+        SearchRequest::parse(Json(payload), QueryType::Words)
+    }
+
+    #[test]
+    fn test_not_found_is_empty() {
+        let res: Result<Vec<u32>> = Err(RestError::NotFound);
+        assert_eq!(or_empty(res), Ok(vec![]));
+
+        let res: Result<Vec<u32>> = Err(RestError::Timeout);
+        assert_eq!(or_empty(res), Err(RestError::Timeout));
+    }
+
+    #[test]
+    fn test_malformed_request() {
+        assert!(parse(r#"{"query": "すし"}"#).is_ok());
+        assert!(parse(r#"{"query": "🍣"}"#).unwrap().only_symbols);
+
+        let query = parse(r#"{"query": "すし", "order": "frequency"}"#).unwrap();
+        assert_eq!(query.order, Some(NamedOrder::Frequency));
+
+        for payload in [
+            r#"{"query": ""}"#,
+            r#"{"query": "すし", "name_type": "unknown"}"#,
+            r#"{"query": "すし", "order": "unknown"}"#,
+        ] {
+            let err = parse(payload).unwrap_err();
+            assert_eq!(err, RestError::BadRequest);
+            assert_eq!(err.status_code(), StatusCode::BAD_REQUEST);
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_concurrent_search_limit() {
+        let permits = Arc::new(Semaphore::new(2));
+        let running = Arc::new(AtomicUsize::new(0));
+        let max_running = Arc::new(AtomicUsize::new(0));
+
+        let searches = (0..6).map(|i| {
+            let running = running.clone();
+            let max_running = max_running.clone();
+            with_permit(permits.clone(), move || {
+                let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                max_running.fetch_max(now, Ordering::SeqCst);
+                thread::sleep(Duration::from_millis(20));
+                running.fetch_sub(1, Ordering::SeqCst);
+                Ok(i)
+            })
+        });
+
+        let res = futures::future::join_all(searches).await;
+        assert!(res.iter().all(|i| i.is_ok()));
+        assert!(max_running.load(Ordering::SeqCst) <= 2);
+        assert_eq!(permits.available_permits(), 2);
+    }
+
+    #[actix_web::test]
+    async fn test_timed_out_search_keeps_permit() {
+        let permits = Arc::new(Semaphore::new(1));
+
+        let search = with_permit(permits.clone(), || {
+            thread::sleep(Duration::from_millis(200));
+            Ok(())
+        });
+        let res = timeout(Duration::from_millis(20), search).await;
+        assert!(res.is_err());
+
+        // The search is still running on the thread pool and holds the only permit
+        assert_eq!(permits.available_permits(), 0);
+
+        let next = with_permit(permits.clone(), || Ok(()));
+        assert!(timeout(Duration::from_secs(5), next).await.is_ok());
+        assert_eq!(permits.available_permits(), 1);
+    }
+}
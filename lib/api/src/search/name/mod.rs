@@ -1,15 +1,36 @@
-use actix_web::web::{self, Json};
+use std::time::Instant;
+
+use actix_web::web::{Data, Json};
+use config::Config;
 use search::query_parser::QueryType::Kanji;
 
 use self::response::Response;
 
-use super::{Result, SearchRequest};
+use super::{
+    envelope::{Envelope, ResultSource},
+    or_empty, with_timeout, Result, SearchRequest,
+};
 
 pub mod response;
 
 /// Do a name search via API
-pub async fn name_search(payload: Json<SearchRequest>) -> Result<Json<Response>> {
+pub async fn name_search(
+    payload: Json<SearchRequest>,
+    config: Data<Config>,
+) -> Result<Json<Envelope<Response>>> {
     let query = SearchRequest::parse(payload, Kanji)?;
-    let result = web::block(move || search::name::search(&query)).await??;
-    Ok(Json(result.items.into()))
+    let query_str = query.query.clone();
+
+    let start = Instant::now();
+    let result = or_empty(with_timeout(&config, move || search::name::search(&query)).await)?;
+    let took = start.elapsed();
+
+    let empty = result.items.is_empty();
+    Ok(Json(Envelope::new(
+        query_str,
+        took,
+        ResultSource::Exact,
+        result.items.into(),
+        empty,
+    )))
 }
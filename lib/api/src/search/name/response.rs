@@ -1,12 +1,12 @@
 use resources::{models::names, parse::jmnedict::name_type::NameType};
 use serde::Serialize;
 
-#[derive(Serialize)]
+#[derive(Serialize, Debug, Clone, PartialEq)]
 pub struct Response {
     names: Vec<Name>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, Debug, Clone, PartialEq)]
 pub struct Name {
     pub kana: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -1,17 +1,21 @@
-use actix_web::web::{self, Json};
+use actix_web::web::{Data, Json};
+use config::Config;
 use search::query_parser::QueryType::Kanji;
 
 use self::response::Response;
 
-use super::{Result, SearchRequest};
+use super::{with_timeout, Result, SearchRequest};
 
 pub mod response;
 
 /// Do a Sentence search via API
-pub async fn sentence_search(payload: Json<SearchRequest>) -> Result<Json<Response>> {
+pub async fn sentence_search(
+    payload: Json<SearchRequest>,
+    config: Data<Config>,
+) -> Result<Json<Response>> {
     let query = SearchRequest::parse(payload, Kanji)?;
 
-    let result = web::block(move || search::sentence::search(&query)).await??;
+    let result = with_timeout(&config, move || search::sentence::search(&query)).await?;
 
     Ok(Json(
         result
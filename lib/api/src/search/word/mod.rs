@@ -1,17 +1,137 @@
 pub mod response;
 
+use std::time::Instant;
+
 use self::response::Response;
 
-use super::{Result, SearchRequest};
+use super::{
+    envelope::{Envelope, ResultSource},
+    or_empty, with_timeout, Result, SearchRequest,
+};
+
+use actix_web::web::{Data, Json, Path, Query};
+use config::Config;
+use error::api_error::RestError;
+use resources::parse::jmdict::languages::Language;
+use search::{query_parser::QueryType::Words, word::result::WordResult};
+use serde::Deserialize;
+
+/// Query parameters of a word by sequence id request
+#[derive(Deserialize)]
+pub struct SeqRequest {
+    #[serde(default)]
+    language: Language,
 
-use actix_web::web::{self, Json};
-use search::query_parser::QueryType::Words;
+    #[serde(default)]
+    no_english: bool,
+
+    /// Show the senses of any available language if the word has none in the requested
+    /// language or english
+    #[serde(default)]
+    language_fallback: bool,
+}
 
 /// Do a word search via API
-pub async fn word_search(payload: Json<SearchRequest>) -> Result<Json<Response>> {
+pub async fn word_search(
+    payload: Json<SearchRequest>,
+    config: Data<Config>,
+) -> Result<Json<Envelope<Response>>> {
+    let include_furigana = payload.include_furigana;
+    let include_raw_tags = payload.include_raw_tags;
+    let load_kanji_info = payload.load_kanji_info;
+    let include_example_count = payload.include_example_count;
     let query = SearchRequest::parse(payload, Words)?;
+    let query_str = query.query.clone();
+    let settings = query.settings;
+
+    let start = Instant::now();
+    let result = or_empty(with_timeout(&config, move || search::word::search(&query)).await)?;
+    let took = start.elapsed();
+
+    let source = result_source(&result);
+    let empty = result.items.is_empty();
+
+    let mut response = Response::from(result);
+    if include_furigana {
+        response.add_furigana();
+    }
+    if include_raw_tags {
+        response.add_raw_tags();
+    }
+    if !load_kanji_info {
+        response.skip_kanji();
+    }
+    if include_example_count {
+        response.add_example_counts(|word| {
+            search::sentence::example_count(word, settings).map(|i| i.value)
+        });
+    }
+
+    Ok(Json(Envelope::new(
+        query_str, took, source, response, empty,
+    )))
+}
+
+/// Get a single word by its sequence id via API
+pub async fn word_by_seq(seq: Path<u32>, args: Query<SeqRequest>) -> Result<Json<response::Word>> {
+    let show_english = !args.no_english;
+    let word = search::word::by_seq(
+        seq.into_inner(),
+        args.language,
+        show_english,
+        args.language_fallback,
+    )
+    .ok_or(RestError::NotFound)?;
+
+    let mut response = response::Word::from(&word);
+    response.set_fallback_language(word.fallback_language(args.language, show_english));
+
+    Ok(Json(response))
+}
+
+/// Returns the `ResultSource` of a word search
+fn result_source(result: &WordResult) -> ResultSource {
+    if result.suggestion.is_some() {
+        ResultSource::Suggestion
+    } else if result.used_fallback {
+        ResultSource::Fallback
+    } else {
+        ResultSource::Exact
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use search::word::result::InflectionInformation;
+
+    #[test]
+    fn test_result_source() {
+        // 食べた deinflected to 食べる
+        let deinflected = WordResult {
+            count: 1,
+            inflection_info: Some(InflectionInformation {
+                lexeme: "食べる".to_string(),
+                forms: vec![],
+            }),
+            searched_query: "食べた".to_string(),
+            used_fallback: true,
+            ..Default::default()
+        };
+        assert_eq!(result_source(&deinflected), ResultSource::Fallback);
 
-    let result = web::block(move || search::word::search(&query)).await??;
+        // Sentence searches look up a single morpheme of the query
+        let sentence = WordResult {
+            count: 1,
+            searched_query: "猫".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(result_source(&sentence), ResultSource::Exact);
 
-    Ok(Json(result.into()))
+        let suggestion = WordResult {
+            suggestion: Some("たべる".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(result_source(&suggestion), ResultSource::Suggestion);
+    }
 }
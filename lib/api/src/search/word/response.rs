@@ -1,5 +1,8 @@
+use japanese::{furigana::SentencePart, JapaneseExt};
+use localization::traits::Translatable;
 use resources::parse::jmdict::{
-    dialect::Dialect, field::Field, languages::Language, misc::Misc, part_of_speech::PartOfSpeech,
+    dialect::Dialect, field::Field, gtype::GType, information::Information, languages::Language,
+    misc::Misc, part_of_speech::PartOfSpeech, Gairaigo,
 };
 
 use search::word::result::{Item, WordResult};
@@ -10,8 +13,15 @@ use crate::search::kanji::response::Kanji;
 /// The API response struct for a word search
 #[derive(Serialize)]
 pub struct Response {
-    kanji: Vec<Kanji>,
+    /// Kanji used in the words. Omitted if loading kanji has been skipped
+    #[serde(skip_serializing_if = "Option::is_none")]
+    kanji: Option<Vec<Kanji>>,
     words: Vec<Word>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    suggestion: Option<String>,
+    /// Description of the inflections of the query if it has been deinflected
+    #[serde(skip_serializing_if = "Option::is_none")]
+    inflection: Option<String>,
 }
 
 /// Represents a single Word result with 1 (main) Japanese reading and n glosses
@@ -22,8 +32,19 @@ pub struct Word {
     senses: Vec<Sense>,
     #[serde(skip_serializing_if = "Option::is_none")]
     alt_readings: Option<Vec<Reading>>,
+    /// Spellings only differing in their okurigana, eg. 表わす for 表す
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    also_written: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     audio: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    furigana: Option<Vec<SentencePart>>,
+    /// Estimated amount of example sentences containing the word
+    #[serde(skip_serializing_if = "Option::is_none")]
+    example_count: Option<u32>,
+    /// The language of the senses if neither the requested language nor english was available
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fallback_language: Option<Language>,
 }
 
 #[derive(Serialize)]
@@ -33,11 +54,21 @@ pub struct Reading {
     kanji: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     furigana: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    kana_info: Option<Vec<Information>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    kanji_info: Option<Vec<Information>>,
 }
 
 #[derive(Serialize)]
 pub struct Sense {
+    /// Number of the sense among the senses of its language in the dictionary, starting at 1
+    #[serde(skip_serializing_if = "Option::is_none")]
+    number: Option<usize>,
     glosses: Vec<String>,
+    /// Types of the glosses at the same positions. Omitted if no gloss has a type
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gloss_types: Option<Vec<Option<GType>>>,
     pos: Vec<PartOfSpeech>,
     language: Language,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -52,6 +83,68 @@ pub struct Sense {
     misc: Option<Misc>,
     #[serde(skip_serializing_if = "Option::is_none")]
     xref: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    related: Vec<WordRef>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    loan_source: Option<Gairaigo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    raw_tags: Option<RawTags>,
+}
+
+/// The raw JMdict codes of a senses tags along with their names
+#[derive(Serialize)]
+pub struct RawTags {
+    pos: Vec<RawTag>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    misc: Option<RawTag>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    field: Option<RawTag>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dialect: Option<RawTag>,
+}
+
+/// A single JMdict tag, eg. `v5k` (Godan verb with 'ku' ending)
+#[derive(Serialize)]
+pub struct RawTag {
+    code: String,
+    name: &'static str,
+}
+
+impl RawTag {
+    #[inline]
+    fn new<T: Translatable + AsRef<str>>(tag: &T) -> Self {
+        Self {
+            code: tag.as_ref().to_string(),
+            name: tag.get_id(),
+        }
+    }
+}
+
+impl From<&PartOfSpeech> for RawTag {
+    #[inline]
+    fn from(pos: &PartOfSpeech) -> Self {
+        Self {
+            code: (*pos).into(),
+            name: pos.get_id(),
+        }
+    }
+}
+
+/// A reference to another word
+#[derive(Serialize)]
+pub struct WordRef {
+    sequence: u32,
+    reading: String,
+}
+
+impl From<&resources::models::words::Word> for WordRef {
+    #[inline]
+    fn from(word: &resources::models::words::Word) -> Self {
+        Self {
+            sequence: word.sequence,
+            reading: word.get_reading().reading.clone(),
+        }
+    }
 }
 
 impl From<&resources::models::words::Sense> for Sense {
@@ -64,8 +157,16 @@ impl From<&resources::models::words::Sense> for Sense {
             .map(|i| i.gloss.clone())
             .collect::<Vec<_>>();
 
+        let gloss_types = sense
+            .glosses
+            .iter()
+            .any(|i| i.g_type.is_some())
+            .then(|| sense.glosses.iter().map(|i| i.g_type).collect());
+
         Self {
+            number: None,
             glosses,
+            gloss_types,
             pos,
             language: sense.language,
             dialect: sense.dialect,
@@ -74,6 +175,9 @@ impl From<&resources::models::words::Sense> for Sense {
             antonym: sense.antonym.as_ref().map(|i| i.clone()),
             misc: sense.misc,
             xref: sense.xref.as_ref().map(|i| i.clone()),
+            related: resolve_xref(sense),
+            loan_source: sense.gairaigo.clone(),
+            raw_tags: None,
         }
     }
 }
@@ -84,8 +188,17 @@ impl From<&resources::models::words::Word> for Word {
         let kanji = word.reading.kanji.as_ref().map(|i| i.reading.clone());
         let kana = word.reading.kana.clone().reading;
         let furigana = word.furigana.clone();
+        let kana_info = word.reading.kana.reading_info.clone();
+        let kanji_info = word
+            .reading
+            .kanji
+            .as_ref()
+            .and_then(|i| i.reading_info.clone());
 
-        let senses = word.senses.iter().map(|i| Sense::from(i)).collect();
+        let mut senses: Vec<Sense> = word.senses.iter().map(|i| Sense::from(i)).collect();
+        for (sense, number) in senses.iter_mut().zip(sense_numbers(word)) {
+            sense.number = number;
+        }
 
         Self {
             common: word.is_common(),
@@ -93,13 +206,23 @@ impl From<&resources::models::words::Word> for Word {
                 kanji,
                 kana,
                 furigana,
+                kana_info,
+                kanji_info,
             },
             senses,
             alt_readings: None,
+            also_written: word
+                .okurigana_variants()
+                .into_iter()
+                .map(|i| i.to_string())
+                .collect(),
             audio: word
                 .audio_file("mp3")
                 .as_ref()
                 .map(|i| format!("/audio/{}", i)),
+            furigana: None,
+            example_count: None,
+            fallback_language: None,
         }
     }
 }
@@ -107,13 +230,111 @@ impl From<&resources::models::words::Word> for Word {
 impl From<WordResult> for Response {
     #[inline]
     fn from(wres: WordResult) -> Self {
-        let kanji = convert_kanji(&wres);
+        let kanji = Some(convert_kanji(&wres));
         let words = convert_words(&wres);
+        let inflection = wres
+            .inflection_info
+            .as_ref()
+            .map(|i| i.explain(localization::language::Language::English));
+
+        Self {
+            kanji,
+            words,
+            suggestion: wres.suggestion,
+            inflection,
+        }
+    }
+}
+
+impl Response {
+    /// Adds aligned furigana to all words having furigana available
+    pub fn add_furigana(&mut self) {
+        for word in self.words.iter_mut() {
+            word.add_furigana();
+        }
+    }
+
+    /// Sets the example sentence count of all words to the count `count_fn` returns for the
+    /// words kanji (or kana if there is none) reading
+    pub fn add_example_counts<F: Fn(&str) -> Option<u32>>(&mut self, count_fn: F) {
+        for word in self.words.iter_mut() {
+            let reading = word.reading.kanji.as_ref().unwrap_or(&word.reading.kana);
+            word.example_count = count_fn(reading);
+        }
+    }
+
+    /// Omits the kanji from the response to indicate that loading them has been skipped
+    pub fn skip_kanji(&mut self) {
+        self.kanji = None;
+    }
+
+    /// Adds the raw JMdict tags to all senses
+    pub fn add_raw_tags(&mut self) {
+        for sense in self.words.iter_mut().flat_map(|i| i.senses.iter_mut()) {
+            sense.add_raw_tags();
+        }
+    }
+}
 
-        Self { kanji, words }
+impl Sense {
+    /// Sets the raw JMdict codes of the senses tags
+    fn add_raw_tags(&mut self) {
+        self.raw_tags = Some(RawTags {
+            pos: self.pos.iter().map(RawTag::from).collect(),
+            misc: self.misc.as_ref().map(RawTag::new),
+            field: self.field.as_ref().map(RawTag::new),
+            dialect: self.dialect.as_ref().map(RawTag::new),
+        });
     }
 }
 
+impl Word {
+    /// Tags the word with the language used as fallback for its senses
+    #[inline]
+    pub fn set_fallback_language(&mut self, language: Option<Language>) {
+        self.fallback_language = language;
+    }
+
+    /// Sets the aligned furigana parts from the words furigana string
+    fn add_furigana(&mut self) {
+        let needs_furigana = self.reading.kanji.as_deref().map(|i| i.needs_furigana());
+        if !needs_furigana.unwrap_or(false) {
+            return;
+        }
+
+        self.furigana = self.reading.furigana.as_ref().map(|furigana| {
+            japanese::furigana::from_str(furigana)
+                .filter(|i| i.kanji.is_some() || !i.kana.is_empty())
+                .map(SentencePart::from)
+                .collect()
+        });
+    }
+}
+
+/// Returns the numbers of the senses of `word` within the stored word, which stay the same
+/// regardless of the senses filtered out for a search
+fn sense_numbers(word: &resources::models::words::Word) -> Vec<Option<usize>> {
+    if word.senses.is_empty() {
+        return vec![];
+    }
+
+    let stored = resources::get().words().by_sequence(word.sequence);
+    word.senses
+        .iter()
+        .map(|sense| stored?.sense_number(sense))
+        .collect()
+}
+
+/// Resolves the `xref` of `sense` into references of the words it points to
+fn resolve_xref(sense: &resources::models::words::Sense) -> Vec<WordRef> {
+    sense
+        .get_xref_target()
+        .and_then(|xref| resources::get().words().by_xref(&xref))
+        .map(WordRef::from)
+        .into_iter()
+        .collect()
+}
+
 #[inline]
 fn convert_kanji(wres: &WordResult) -> Vec<Kanji> {
     wres.items
@@ -135,3 +356,124 @@ fn convert_words(wres: &WordResult) -> Vec<Word> {
         })
         .collect()
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_word_furigana_json() {
+        let mut word = Word {
+            reading: Reading {
+                kana: "がくせい".to_string(),
+                kanji: Some("学生".to_string()),
+                furigana: Some("[学生|がく|せい]".to_string()),
+                kana_info: None,
+                kanji_info: None,
+            },
+            common: true,
+            senses: vec![],
+            alt_readings: None,
+            also_written: vec![],
+            audio: None,
+            furigana: None,
+            example_count: None,
+            fallback_language: None,
+        };
+        word.add_furigana();
+
+        let json = serde_json::to_value(&word).unwrap();
+        assert_eq!(
+            json["furigana"],
+            serde_json::json!([
+                {"type": "kanji", "kanji": "学", "reading": "がく"},
+                {"type": "kanji", "kanji": "生", "reading": "せい"},
+            ])
+        );
+    }
+
+    #[test]
+    fn test_word_irregular_kanji_json() {
+        let word = resources::models::words::Word {
+            reading: resources::models::words::Reading {
+                kana: resources::models::words::Dict {
+                    reading: "こんにちわ".to_string(),
+                    ..Default::default()
+                },
+                kanji: Some(resources::models::words::Dict {
+                    reading: "今日わ".to_string(),
+                    kanji: true,
+                    reading_info: Some(vec![Information::IrregularKanji]),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let json = serde_json::to_value(&Word::from(&word)).unwrap();
+        assert_eq!(json["reading"]["kanji_info"], serde_json::json!(["iK"]));
+        assert!(json["reading"].get("kana_info").is_none());
+    }
+
+    #[test]
+    fn test_raw_tags_json() {
+        let mut sense = Sense::from(&resources::models::words::Sense {
+            part_of_speech: vec![PartOfSpeech::Adverb],
+            misc: Some(Misc::UsuallyWrittenInKana),
+            dialect: Some(Dialect::Kansai),
+            ..Default::default()
+        });
+
+        let json = serde_json::to_value(&sense).unwrap();
+        assert!(json.get("raw_tags").is_none());
+
+        sense.add_raw_tags();
+        let json = serde_json::to_value(&sense).unwrap();
+        assert_eq!(
+            json["raw_tags"],
+            serde_json::json!({
+                "pos": [{"code": "adv", "name": "Adverb"}],
+                "misc": {"code": "uk", "name": Misc::UsuallyWrittenInKana.get_id()},
+                "dialect": {"code": "ksb", "name": Dialect::Kansai.get_id()},
+            })
+        );
+    }
+
+    #[test]
+    fn test_example_count_json() {
+        let word = |kana: &str, kanji: Option<&str>| {
+            Word::from(&resources::models::words::Word {
+                reading: resources::models::words::Reading {
+                    kana: resources::models::words::Dict {
+                        reading: kana.to_string(),
+                        ..Default::default()
+                    },
+                    kanji: kanji.map(|i| resources::models::words::Dict {
+                        reading: i.to_string(),
+                        kanji: true,
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+        };
+
+        let mut response = Response {
+            kanji: None,
+            words: vec![word("がくせい", Some("学生")), word("ここ", None)],
+            suggestion: None,
+            inflection: None,
+        };
+        response.add_example_counts(|reading| match reading {
+            "学生" => Some(42),
+            _ => None,
+        });
+
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["words"][0]["example_count"], 42);
+        assert!(json["words"][1].get("example_count").is_none());
+        assert!(json.get("kanji").is_none());
+    }
+}
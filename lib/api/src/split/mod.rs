@@ -0,0 +1,78 @@
+use actix_web::web::Json;
+use error::api_error::RestError;
+use japanese::CharType;
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize)]
+pub struct Request {
+    pub text: String,
+}
+
+#[derive(Serialize)]
+pub struct Response {
+    pub segments: Vec<Segment>,
+}
+
+/// A part of the text consisting of characters of the same type
+#[derive(Debug, Serialize, PartialEq)]
+pub struct Segment {
+    pub text: String,
+    pub char_type: CharType,
+    /// Byte offset of the segment within the requested text
+    pub start: usize,
+    /// Byte offset of the end of the segment (exclusive)
+    pub end: usize,
+}
+
+/// Split text into kana, kanji and other segments endpoint
+pub async fn split(payload: Json<Request>) -> Result<Json<Response>, RestError> {
+    let segments = split_text(&payload.text);
+    Ok(Json(Response { segments }))
+}
+
+/// Splits `text` into segments of the same `CharType`
+fn split_text(text: &str) -> Vec<Segment> {
+    japanese::split_by_char_type(text)
+        .into_iter()
+        .map(|(char_type, range)| Segment {
+            text: text[range.clone()].to_string(),
+            char_type,
+            start: range.start,
+            end: range.end,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn segment(text: &str, char_type: CharType, start: usize, end: usize) -> Segment {
+        Segment {
+            text: text.to_string(),
+            char_type,
+            start,
+            end,
+        }
+    }
+
+    #[test]
+    fn test_split_text() {
+        assert_eq!(
+            split_text("漢字とカナ"),
+            vec![
+                segment("漢字", CharType::Kanji, 0, 6),
+                segment("とカナ", CharType::Kana, 6, 15),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_segment_json() {
+        let json = serde_json::to_string(&segment("漢字", CharType::Kanji, 0, 6)).unwrap();
+        assert_eq!(
+            json,
+            r#"{"text":"漢字","char_type":"kanji","start":0,"end":6}"#
+        );
+    }
+}
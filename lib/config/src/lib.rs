@@ -46,6 +46,11 @@ pub struct SearchConfig {
     pub indexes_source: Option<String>,
     pub report_queries_after: Option<u64>,
     pub search_timeout: Option<u64>,
+    pub max_concurrent_searches: Option<usize>,
+    /// Threshold in milliseconds after which searches get logged as slow
+    pub slow_query_threshold: Option<u64>,
+    /// Amount of kanji reading search results to cache. `0` disables the cache
+    pub reading_cache_size: Option<usize>,
 }
 
 impl Config {
@@ -59,6 +64,29 @@ impl Config {
         Duration::from_secs(sec)
     }
 
+    /// Returns the configured max amount of concurrently running API searches or the default
+    /// value `10`
+    pub fn get_max_concurrent_searches(&self) -> usize {
+        self.search
+            .as_ref()
+            .and_then(|i| i.max_concurrent_searches)
+            .unwrap_or(10)
+            .max(1)
+    }
+
+    /// Returns the configured duration after which searches get logged as slow, if set
+    pub fn get_slow_query_threshold(&self) -> Option<Duration> {
+        self.search
+            .as_ref()
+            .and_then(|i| i.slow_query_threshold)
+            .map(Duration::from_millis)
+    }
+
+    /// Returns the configured amount of kanji reading search results to cache, if set
+    pub fn get_reading_cache_size(&self) -> Option<usize> {
+        self.search.as_ref().and_then(|i| i.reading_cache_size)
+    }
+
     /// Returns the configured suggestion timeout or its default value if not set
     pub fn get_suggestion_timeout(&self) -> Duration {
         let amount = self
@@ -105,6 +105,8 @@ impl From<super::Error> for RestError {
     fn from(err: super::Error) -> Self {
         match err {
             crate::Error::NotFound => Self::NotFound,
+            crate::Error::ParseError => Self::BadRequest,
+            crate::Error::Timeout => Self::Timeout,
             _ => Self::Internal,
         }
     }
@@ -13,6 +13,7 @@ pub enum Error {
     Undefined,
     IoError(std::io::Error),
     Unexpected,
+    Timeout,
 }
 
 impl From<std::io::Error> for Error {
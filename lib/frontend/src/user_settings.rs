@@ -42,6 +42,11 @@ pub(super) fn parse(request: &HttpRequest) -> UserSettings {
         .and_then(|i| Some(i.value() == "true"))
         .unwrap_or_else(|| UserSettings::default().show_example_sentences);
 
+    let prefer_monolingual = request
+        .cookie("prefer_monolingual")
+        .and_then(|i| i.value().parse().ok())
+        .unwrap_or_else(|| UserSettings::default().prefer_monolingual);
+
     let cookies_enabled = request
         .cookie("allow_cookies")
         .and_then(|i| {
@@ -59,6 +64,7 @@ pub(super) fn parse(request: &HttpRequest) -> UserSettings {
         page_size: items_per_page,
         kanji_page_size: items_per_kanji_page,
         show_example_sentences: example_sentences_enabled,
+        prefer_monolingual,
         ..Default::default()
     }
 }
@@ -1,5 +1,5 @@
 use super::{
-    super::{text_parts, JapaneseExt},
+    super::{is_small_ke, text_parts, JapaneseExt, SMALL_KE_READINGS},
     calc_kanji_readings, from_str,
 };
 use crate::utils::real_string_len;
@@ -56,7 +56,10 @@ pub fn retrieve_readings<R: RetrieveKanji>(
         );
     }
 
-    let kanji_items = kanji.chars().filter(|i| i.is_kanji()).collect_vec();
+    let kanji_items = kanji
+        .chars()
+        .filter(|i| i.is_kanji() || i.is_rare_kanji() || is_small_ke(*i))
+        .collect_vec();
     if kanji_items.len() == 1 {
         return Some(vec![(kanji.to_owned(), kana.to_owned())]);
     }
@@ -67,6 +70,10 @@ pub fn retrieve_readings<R: RetrieveKanji>(
     let kanji_readings = kanji_items
         .iter()
         .map(|i| {
+            if is_small_ke(*i) {
+                return Some(SMALL_KE_READINGS.iter().map(|i| i.to_string()).collect());
+            }
+
             let (kun, on) = retrieve(i.to_string())?;
             let kun = kun.map(|i| format_readings(i)).unwrap_or_default();
             let on = on.map(|i| format_readings(i)).unwrap_or_default();
@@ -181,7 +188,8 @@ where
     std::iter::from_fn(move || {
         let curr_part = text_parts.next()?;
 
-        if curr_part.is_kanji() || (curr_part.has_kanji() && curr_part.has_symbol()) {
+        let all_kanji = curr_part.chars().all(|c| c.is_kanji() || c.is_rare_kanji());
+        if all_kanji || (curr_part.has_kanji() && curr_part.has_symbol()) {
             let (kanji, reading) = furi.next()?;
 
             if let Some(readings) = kanji_lookup(&kanji, &reading) {
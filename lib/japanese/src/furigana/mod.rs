@@ -3,12 +3,13 @@ pub mod generate;
 use std::str::CharIndices;
 
 use itertools::{Itertools, MultiPeek};
+use serde::{ser::SerializeStruct, Serialize, Serializer};
 
 use super::JapaneseExt;
 
 /// Represents a single sentence part which either consisting of kana only or kanji and a kana reading
 /// assigned
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct SentencePart {
     pub kana: String,
     pub kanji: Option<String>,
@@ -21,6 +22,38 @@ pub struct SentencePartRef<'a> {
     pub kanji: Option<&'a str>,
 }
 
+impl<'a> From<SentencePartRef<'a>> for SentencePart {
+    #[inline]
+    fn from(part: SentencePartRef<'a>) -> Self {
+        Self {
+            kana: part.kana.to_owned(),
+            kanji: part.kanji.map(|i| i.to_owned()),
+        }
+    }
+}
+
+/// Serializes parts with kanji as `{"type":"kanji","kanji":..,"reading":..}` and kana only parts
+/// as `{"type":"plain","text":..}`
+impl Serialize for SentencePart {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.kanji {
+            Some(ref kanji) => {
+                let mut state = serializer.serialize_struct("SentencePart", 3)?;
+                state.serialize_field("type", "kanji")?;
+                state.serialize_field("kanji", kanji)?;
+                state.serialize_field("reading", &self.kana)?;
+                state.end()
+            }
+            None => {
+                let mut state = serializer.serialize_struct("SentencePart", 2)?;
+                state.serialize_field("type", "plain")?;
+                state.serialize_field("text", &self.kana)?;
+                state.end()
+            }
+        }
+    }
+}
+
 impl SentencePart {
     /// Make the kana reading good looking as furigana text If the kanji count matches with kana
     /// count, a space will be added between each char
@@ -154,6 +187,152 @@ pub fn from_str<'a>(input: &'a str) -> impl Iterator<Item = SentencePartRef<'a>>
     })
 }
 
+/// Parses text with readings in bracket notation into SentenceParts. Plain text between
+/// annotations is returned as kana only parts
+///
+/// Input format: 私[わたし]は学生[がくせい]です
+///
+/// Brackets can be escaped using a backslash. Brackets within a reading are kept as they are
+pub fn parse_bracket_notation(input: &str) -> Vec<SentencePart> {
+    let mut parts = vec![];
+    let mut text = String::new();
+    let mut chars = input.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                if let Some(escaped) = chars.next() {
+                    text.push(escaped);
+                }
+            }
+            '[' => {
+                let mut lookahead = chars.clone();
+                let reading = match read_bracket(&mut lookahead) {
+                    Some(reading) => reading,
+                    None => {
+                        // Unclosed bracket
+                        text.push(c);
+                        continue;
+                    }
+                };
+
+                // The annotated text are all kanji (or letters) in front of the bracket
+                let base_start = text
+                    .char_indices()
+                    .rev()
+                    .take_while(|(_, c)| (c.is_alphanumeric() && !c.is_kana()) || *c == '々')
+                    .last()
+                    .map(|(pos, _)| pos);
+
+                let base_start = match base_start {
+                    Some(b) => b,
+                    None => {
+                        text.push(c);
+                        continue;
+                    }
+                };
+                chars = lookahead;
+
+                let base = text.split_off(base_start);
+
+                // Spaces can be used to separate an annotated text from plain text in front of it
+                if text.ends_with(' ') {
+                    text.pop();
+                }
+
+                if !text.is_empty() {
+                    parts.push(default_pair("", &text));
+                    text.clear();
+                }
+
+                if reading.is_empty() {
+                    parts.push(default_pair("", &base));
+                } else {
+                    parts.push(default_pair(&base, &reading));
+                }
+            }
+            _ => text.push(c),
+        }
+    }
+
+    if !text.is_empty() {
+        parts.push(default_pair("", &text));
+    }
+
+    parts
+}
+
+/// Removes readings written in bracket notation from `input`, leaving only the annotated text
+///
+/// Example: 私[わたし]は学生[がくせい]です => 私は学生です
+pub fn strip_furigana(input: &str) -> String {
+    parse_bracket_notation(input)
+        .into_iter()
+        .map(|part| part.kanji.unwrap_or(part.kana))
+        .collect()
+}
+
+/// Removes html ruby annotations from `input`, leaving only the annotated text. Readings in `<rt>`
+/// and fallback parenthesis in `<rp>` tags are removed, all other tags are dropped but their
+/// content is kept
+///
+/// Example: <ruby>漢字<rp>(</rp><rt>かんじ</rt><rp>)</rp></ruby> => 漢字
+pub fn strip_ruby(input: &str) -> String {
+    let mut text = String::new();
+    let mut rest = input;
+
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let end = match rest.find('>') {
+            Some(end) => end,
+            None => break,
+        };
+        let tag = rest[1..end].split_whitespace().next().unwrap_or_default();
+        let tag = tag.to_ascii_lowercase();
+        rest = &rest[end + 1..];
+
+        if tag == "rt" || tag == "rp" {
+            let closing = format!("</{}>", tag);
+            rest = match rest.to_ascii_lowercase().find(&closing) {
+                Some(pos) => &rest[pos + closing.len()..],
+                None => "",
+            };
+        }
+    }
+
+    text.push_str(rest);
+    text
+}
+
+/// Reads the content of a bracket until its closing bracket. Returns `None` if the bracket wasn't
+/// closed
+fn read_bracket(chars: &mut std::str::Chars) -> Option<String> {
+    let mut content = String::new();
+    let mut depth = 1;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => content.push(chars.next()?),
+            '[' => {
+                depth += 1;
+                content.push(c);
+            }
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(content);
+                }
+                content.push(c);
+            }
+            _ => content.push(c),
+        }
+    }
+
+    None
+}
+
 /// Check wether the passed furigana pairs are representing the given kana text or not
 pub fn check_pairs(pars: &[SentencePart], kana: &str) -> bool {
     let s: String = pars.iter().map(|i| i.kana.clone()).collect();
@@ -364,7 +543,7 @@ where
 
     arr[offset..]
         .iter()
-        .any(|i| i.is_kanji() || i.is_roman_letter())
+        .any(|i| i.has_kanji() || i.is_roman_letter())
 }
 
 /// Checks whether 'arr' starts with a*b or not
@@ -408,7 +587,7 @@ where
 {
     let mut kanji_iter = kanji_iter.clone();
     let kanji = kanji_iter
-        .take_while_ref(|i| i.is_kanji() || i.is_symbol() || i.is_roman_letter())
+        .take_while_ref(|i| i.has_kanji() || i.is_symbol() || i.is_roman_letter())
         .collect::<Vec<_>>();
     let kana = kanji_iter
         .take_while_ref(|i| i.is_kana())
@@ -426,7 +605,7 @@ where
     loop {
         if kanji_iter
             .next()
-            .map(|i| i.is_kanji() || i.is_symbol() || i.is_roman_letter())
+            .map(|i| i.has_kanji() || i.is_symbol() || i.is_roman_letter())
             .unwrap_or(true)
         {
             break i;
@@ -435,3 +614,109 @@ where
         i += 1;
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn part(kanji: Option<&str>, kana: &str) -> SentencePart {
+        SentencePart {
+            kanji: kanji.map(|i| i.to_string()),
+            kana: kana.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_bracket_notation() {
+        assert_eq!(
+            parse_bracket_notation("私[わたし]は学生[がくせい]です"),
+            vec![
+                part(Some("私"), "わたし"),
+                part(None, "は"),
+                part(Some("学生"), "がくせい"),
+                part(None, "です"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_bracket_notation_separator() {
+        assert_eq!(
+            parse_bracket_notation("今日 学校[がっこう]"),
+            vec![part(None, "今日"), part(Some("学校"), "がっこう")]
+        );
+    }
+
+    #[test]
+    fn test_parse_bracket_notation_escaped() {
+        assert_eq!(
+            parse_bracket_notation("\\[注\\]猫[ねこ]"),
+            vec![part(None, "[注]"), part(Some("猫"), "ねこ")]
+        );
+        assert_eq!(
+            parse_bracket_notation("漢字[かん[じ]]"),
+            vec![part(Some("漢字"), "かん[じ]")]
+        );
+    }
+
+    #[test]
+    fn test_strip_furigana() {
+        assert_eq!(strip_furigana("私[わたし]"), "私");
+        assert_eq!(
+            strip_furigana("私[わたし]は学生[がくせい]です"),
+            "私は学生です"
+        );
+        assert_eq!(strip_furigana("ねこ"), "ねこ");
+    }
+
+    #[test]
+    fn test_strip_ruby() {
+        assert_eq!(
+            strip_ruby("<ruby>漢字<rp>(</rp><rt>かんじ</rt><rp>)</rp></ruby>"),
+            "漢字"
+        );
+        assert_eq!(
+            strip_ruby(
+                "<ruby>私<rt>わたし</rt></ruby>は<ruby><rb>学生</rb><rt>がくせい</rt></ruby>"
+            ),
+            "私は学生"
+        );
+        assert_eq!(strip_ruby("ねこ"), "ねこ");
+    }
+
+    #[test]
+    fn test_serialize() {
+        let parts = vec![part(Some("学生"), "がくせい"), part(None, "です")];
+        assert_eq!(
+            serde_json::to_string(&parts).unwrap(),
+            r#"[{"type":"kanji","kanji":"学生","reading":"がくせい"},{"type":"plain","text":"です"}]"#
+        );
+    }
+
+    #[test]
+    fn test_retrieve_readings_small_ke() {
+        let readings =
+            |kun: &str, on: &str| Some((Some(vec![kun.to_string()]), Some(vec![on.to_string()])));
+        let mut retrieve = |kanji: String| match kanji.as_str() {
+            "霞" => readings("かすみ", "カ"),
+            "関" => readings("せき", "カン"),
+            _ => None,
+        };
+
+        let found = generate::retrieve_readings(&mut retrieve, "霞ヶ関", "かすみがせき");
+        let expected = [("霞", "かすみ"), ("ヶ", "が"), ("関", "せき")]
+            .iter()
+            .map(|(k, r)| (k.to_string(), r.to_string()))
+            .collect::<Vec<_>>();
+        assert_eq!(found, Some(expected));
+    }
+
+    #[test]
+    fn test_parse_bracket_notation_plain() {
+        assert_eq!(parse_bracket_notation("ねこ"), vec![part(None, "ねこ")]);
+        assert_eq!(
+            parse_bracket_notation("猫[ねこ"),
+            vec![part(None, "猫[ねこ")]
+        );
+    }
+}
@@ -1,4 +1,5 @@
-use localization::traits::Translatable;
+use localization::{language::Language, traits::Translatable};
+use once_cell::sync::Lazy;
 use strum_macros::AsRefStr;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, AsRefStr)]
@@ -14,6 +15,20 @@ pub enum Inflection {
     CausativePassive,
     Imperative,
     Tai,
+    /// ている
+    Progressive,
+    /// てある
+    Resultative,
+    /// ておく
+    Preparatory,
+    /// てしまう
+    Completive,
+    /// ていく
+    TeIku,
+    /// てくる
+    TeKuru,
+    /// Colloquially contracted auxiliary, eg. てる instead of ている
+    Contracted,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -49,6 +64,13 @@ impl Translatable for Inflection {
             Inflection::CausativePassive => "CausativePassive",
             Inflection::Imperative => "Imperative",
             Inflection::Tai => "Tai",
+            Inflection::Progressive => "Progressive",
+            Inflection::Resultative => "Resultative",
+            Inflection::Preparatory => "Preparatory",
+            Inflection::Completive => "Completive",
+            Inflection::TeIku => "TeIku",
+            Inflection::TeKuru => "TeKuru",
+            Inflection::Contracted => "Contracted",
         }
     }
 
@@ -60,3 +82,425 @@ impl Translatable for Inflection {
         self.pgettext(dict, "inflection", language)
     }
 }
+
+/// Words used to describe an inflection chain in a given language
+struct Phrases {
+    /// Returns the name of a single inflection
+    name: fn(Inflection) -> &'static str,
+    /// Describes the inflections applied on top of the polite form
+    of_polite: fn(&str) -> String,
+    /// Describes the polite form without any further inflections
+    polite: &'static str,
+}
+
+const ENGLISH: Phrases = Phrases {
+    name: english_name,
+    of_polite: |inflections| format!("{} of the polite form", inflections),
+    polite: "polite form",
+};
+
+fn english_name(inflection: Inflection) -> &'static str {
+    match inflection {
+        Inflection::Negative => "negative",
+        Inflection::Polite => "polite",
+        Inflection::Present => "present",
+        Inflection::Past => "past",
+        Inflection::TeForm => "te-form",
+        Inflection::Potential => "potential",
+        Inflection::Passive => "passive",
+        Inflection::Causative => "causative",
+        Inflection::CausativePassive => "causative passive",
+        Inflection::Imperative => "imperative",
+        Inflection::Tai => "tai-form",
+        Inflection::Progressive => "progressive",
+        Inflection::Resultative => "resultative",
+        Inflection::Preparatory => "preparatory",
+        Inflection::Completive => "completive",
+        Inflection::TeIku => "te-iku form",
+        Inflection::TeKuru => "te-kuru form",
+        Inflection::Contracted => "contracted",
+    }
+}
+
+/// Returns the phrases to describe inflections with in `language`. Falls back to english for
+/// languages which aren't supported yet
+fn phrases(language: Language) -> &'static Phrases {
+    match language {
+        Language::English => &ENGLISH,
+        _ => &ENGLISH,
+    }
+}
+
+/// Returns a human readable description of the inflection chain `chain` (starting with the
+/// innermost inflection) in `language`, eg. "negative past of the polite form"
+pub fn explain(chain: &[Inflection], language: Language) -> String {
+    let phrases = phrases(language);
+
+    let (polite, chain) = match chain.split_first() {
+        Some((Inflection::Polite, rest)) => (true, rest),
+        _ => (false, chain),
+    };
+
+    let inflections = chain
+        .iter()
+        .map(|i| (phrases.name)(*i))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    match (polite, inflections.is_empty()) {
+        (true, true) => phrases.polite.to_string(),
+        (true, false) => (phrases.of_polite)(&inflections),
+        (false, _) => inflections,
+    }
+}
+
+/// A possible dictionary form of an inflected word
+#[derive(Clone, Debug, PartialEq)]
+pub struct Deinflection {
+    /// The assumed dictionary form
+    pub base: String,
+    /// All inflections which have been applied to `base`, starting with the innermost one
+    pub inflections: Vec<Inflection>,
+}
+
+/// The kind of form a word is in, used to prevent chaining rules which can't follow each other
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum FormKind {
+    /// A form which can't be inflected any further (eg. past or te-form)
+    Terminal,
+    /// Verbs in dictionary form
+    Verb,
+    /// Forms which inflect like i-adjectives (eg. ない or たい forms)
+    Adjective,
+    /// Polite ます forms
+    Masu,
+}
+
+struct Rule {
+    suffix: String,
+    replacement: String,
+    inflection: Inflection,
+    from: FormKind,
+    to: FormKind,
+    /// Whether the rule removes a contracted auxiliary
+    contracted: bool,
+}
+
+impl Rule {
+    fn new(
+        suffix: &str,
+        replacement: &str,
+        inflection: Inflection,
+        from: FormKind,
+        to: FormKind,
+    ) -> Self {
+        Self {
+            suffix: suffix.to_string(),
+            replacement: replacement.to_string(),
+            inflection,
+            from,
+            to,
+            contracted: false,
+        }
+    }
+
+    /// Marks the rule as removing a contracted auxiliary
+    fn contracted(mut self) -> Self {
+        self.contracted = true;
+        self
+    }
+}
+
+/// Godan verb endings as (u, a, i, e) row
+const GODAN_ROWS: &[(char, char, char, char)] = &[
+    ('う', 'わ', 'い', 'え'),
+    ('く', 'か', 'き', 'け'),
+    ('ぐ', 'が', 'ぎ', 'げ'),
+    ('す', 'さ', 'し', 'せ'),
+    ('つ', 'た', 'ち', 'て'),
+    ('ぬ', 'な', 'に', 'ね'),
+    ('ぶ', 'ば', 'び', 'べ'),
+    ('む', 'ま', 'み', 'め'),
+    ('る', 'ら', 'り', 'れ'),
+];
+
+/// Past and te-form endings of godan verbs
+const GODAN_TA: &[(&str, &str)] = &[
+    ("いた", "く"),
+    ("いだ", "ぐ"),
+    ("した", "す"),
+    ("った", "う"),
+    ("った", "つ"),
+    ("った", "る"),
+    ("んだ", "ぬ"),
+    ("んだ", "ぶ"),
+    ("んだ", "む"),
+];
+
+/// Auxiliary verbs following the te-form of a verb
+const AUXILIARIES: &[(&str, Inflection)] = &[
+    ("いる", Inflection::Progressive),
+    ("ある", Inflection::Resultative),
+    ("おく", Inflection::Preparatory),
+    ("しまう", Inflection::Completive),
+    ("いく", Inflection::TeIku),
+    ("くる", Inflection::TeKuru),
+];
+
+/// Contracted te-form auxiliaries replacing the て (or で) of the te-form
+const CONTRACTED_AUXILIARIES: &[(&str, &str, Inflection)] = &[
+    ("てる", "でる", Inflection::Progressive),
+    ("とく", "どく", Inflection::Preparatory),
+    ("ちゃう", "じゃう", Inflection::Completive),
+    ("てく", "でく", Inflection::TeIku),
+];
+
+static RULES: Lazy<Vec<Rule>> = Lazy::new(build_rules);
+
+fn build_rules() -> Vec<Rule> {
+    use FormKind::*;
+    use Inflection::*;
+
+    let mut rules = vec![
+        // Ichidan verbs
+        Rule::new("ない", "る", Negative, Adjective, Verb),
+        Rule::new("ます", "る", Polite, Masu, Verb),
+        Rule::new("た", "る", Past, Terminal, Verb),
+        Rule::new("て", "る", TeForm, Terminal, Verb),
+        Rule::new("たい", "る", Tai, Adjective, Verb),
+        Rule::new("られる", "る", Potential, Verb, Verb),
+        Rule::new("させる", "る", Causative, Verb, Verb),
+        Rule::new("させられる", "る", CausativePassive, Verb, Verb),
+        Rule::new("ろ", "る", Imperative, Terminal, Verb),
+        // する
+        Rule::new("しない", "する", Negative, Adjective, Verb),
+        Rule::new("します", "する", Polite, Masu, Verb),
+        Rule::new("した", "する", Past, Terminal, Verb),
+        Rule::new("して", "する", TeForm, Terminal, Verb),
+        Rule::new("したい", "する", Tai, Adjective, Verb),
+        Rule::new("できる", "する", Potential, Verb, Verb),
+        Rule::new("される", "する", Passive, Verb, Verb),
+        Rule::new("させる", "する", Causative, Verb, Verb),
+        // 行く has irregular past and te-forms
+        Rule::new("行った", "行く", Past, Terminal, Verb),
+        Rule::new("行って", "行く", TeForm, Terminal, Verb),
+        Rule::new("いった", "いく", Past, Terminal, Verb),
+        Rule::new("いって", "いく", TeForm, Terminal, Verb),
+        // i-adjectives and forms inflecting like them
+        Rule::new("かった", "い", Past, Terminal, Adjective),
+        Rule::new("くない", "い", Negative, Adjective, Adjective),
+        Rule::new("くて", "い", TeForm, Terminal, Adjective),
+        // Polite forms
+        Rule::new("ました", "ます", Past, Terminal, Masu),
+        Rule::new("ません", "ます", Negative, Terminal, Masu),
+    ];
+
+    for (u, a, i, e) in GODAN_ROWS {
+        let u = u.to_string();
+        rules.push(Rule::new(
+            &format!("{}ない", a),
+            &u,
+            Negative,
+            Adjective,
+            Verb,
+        ));
+        rules.push(Rule::new(&format!("{}ます", i), &u, Polite, Masu, Verb));
+        rules.push(Rule::new(&format!("{}たい", i), &u, Tai, Adjective, Verb));
+        rules.push(Rule::new(&format!("{}る", e), &u, Potential, Verb, Verb));
+        rules.push(Rule::new(&format!("{}れる", a), &u, Passive, Verb, Verb));
+        rules.push(Rule::new(&format!("{}せる", a), &u, Causative, Verb, Verb));
+        rules.push(Rule::new(
+            &format!("{}される", a),
+            &u,
+            CausativePassive,
+            Verb,
+            Verb,
+        ));
+        rules.push(Rule::new(&e.to_string(), &u, Imperative, Terminal, Verb));
+    }
+
+    let mut te_forms = vec![
+        ("て".to_string(), "る"),
+        ("して".to_string(), "する"),
+        ("行って".to_string(), "行く"),
+        ("いって".to_string(), "いく"),
+    ];
+
+    for (ending, u) in GODAN_TA {
+        let te = ending.replace('た', "て").replace('だ', "で");
+        rules.push(Rule::new(ending, u, Past, Terminal, Verb));
+        rules.push(Rule::new(&te, u, TeForm, Terminal, Verb));
+        te_forms.push((te, u));
+    }
+
+    for (te, base) in te_forms {
+        for (auxiliary, inflection) in AUXILIARIES {
+            let suffix = format!("{}{}", te, auxiliary);
+            rules.push(Rule::new(&suffix, base, *inflection, Verb, Verb));
+        }
+
+        let (stem, voiced) = match te.strip_suffix('て') {
+            Some(stem) => (stem, false),
+            None => (te.trim_end_matches('で'), true),
+        };
+
+        for (contracted, contracted_voiced, inflection) in CONTRACTED_AUXILIARIES {
+            let auxiliary = if voiced {
+                contracted_voiced
+            } else {
+                contracted
+            };
+            let suffix = format!("{}{}", stem, auxiliary);
+            rules.push(Rule::new(&suffix, base, *inflection, Verb, Verb).contracted());
+        }
+    }
+
+    rules
+}
+
+/// Returns all possible dictionary forms of `word` along with the inflections which would have to
+/// be applied to them in order to get `word`. The returned forms aren't checked against the
+/// dictionary and may not exist at all
+pub fn deinflect(word: &str) -> Vec<Deinflection> {
+    let mut found: Vec<Deinflection> = vec![];
+    let mut queue: Vec<(String, Option<FormKind>, Vec<Inflection>)> =
+        vec![(word.to_string(), None, vec![])];
+
+    while let Some((form, kind, inflections)) = queue.pop() {
+        for rule in RULES.iter() {
+            if kind.map(|k| k != rule.from).unwrap_or(false) || !form.ends_with(&rule.suffix) {
+                continue;
+            }
+
+            let stem = &form[..form.len() - rule.suffix.len()];
+            if stem.is_empty() {
+                continue;
+            }
+
+            let base = format!("{}{}", stem, rule.replacement);
+
+            // Prevent endless loops of rules replacing a suffix with itself
+            if inflections.contains(&rule.inflection) {
+                continue;
+            }
+
+            let mut new_inflections = vec![rule.inflection];
+            if rule.contracted {
+                new_inflections.push(Inflection::Contracted);
+            }
+            new_inflections.extend(inflections.iter().copied());
+
+            if rule.to != FormKind::Masu
+                && !found
+                    .iter()
+                    .any(|i| i.base == base && i.inflections == new_inflections)
+            {
+                found.push(Deinflection {
+                    base: base.clone(),
+                    inflections: new_inflections.clone(),
+                });
+            }
+
+            queue.push((base, Some(rule.to), new_inflections));
+        }
+    }
+
+    // Prefer forms requiring less inflections
+    found.sort_by_key(|i| i.inflections.len());
+    found
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn has_base(word: &str, base: &str, inflections: &[Inflection]) -> bool {
+        deinflect(word)
+            .iter()
+            .any(|i| i.base == base && i.inflections == inflections)
+    }
+
+    #[test]
+    fn test_deinflect_tai() {
+        assert!(has_base("食べたい", "食べる", &[Inflection::Tai]));
+    }
+
+    #[test]
+    fn test_deinflect_negative_past() {
+        assert!(has_base(
+            "行かなかった",
+            "行く",
+            &[Inflection::Negative, Inflection::Past]
+        ));
+    }
+
+    #[test]
+    fn test_deinflect_polite_past() {
+        assert!(has_base(
+            "書きました",
+            "書く",
+            &[Inflection::Polite, Inflection::Past]
+        ));
+    }
+
+    #[test]
+    fn test_deinflect_progressive() {
+        assert!(has_base("食べている", "食べる", &[Inflection::Progressive]));
+        assert!(has_base(
+            "読んでいた",
+            "読む",
+            &[Inflection::Progressive, Inflection::Past]
+        ));
+    }
+
+    #[test]
+    fn test_deinflect_contracted_progressive() {
+        assert!(has_base(
+            "食べてる",
+            "食べる",
+            &[Inflection::Progressive, Inflection::Contracted]
+        ));
+    }
+
+    #[test]
+    fn test_deinflect_preparatory_past() {
+        assert!(has_base(
+            "やっておいた",
+            "やる",
+            &[Inflection::Preparatory, Inflection::Past]
+        ));
+    }
+
+    #[test]
+    fn test_deinflect_contracted_completive() {
+        assert!(has_base(
+            "飲んじゃった",
+            "飲む",
+            &[
+                Inflection::Completive,
+                Inflection::Contracted,
+                Inflection::Past
+            ]
+        ));
+    }
+
+    #[test]
+    fn test_explain() {
+        use Inflection::*;
+
+        let explain = |chain: &[Inflection]| explain(chain, Language::English);
+        assert_eq!(explain(&[Negative, Past]), "negative past");
+        assert_eq!(explain(&[Causative, Passive]), "causative passive");
+        assert_eq!(
+            explain(&[Polite, Negative, Past]),
+            "negative past of the polite form"
+        );
+        assert_eq!(explain(&[Polite]), "polite form");
+        assert_eq!(explain(&[]), "");
+    }
+
+    #[test]
+    fn test_deinflect_plain() {
+        assert!(deinflect("食").is_empty());
+    }
+}
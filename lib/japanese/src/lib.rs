@@ -4,10 +4,14 @@ pub mod jp_parsing;
 pub mod accent;
 pub mod furigana;
 pub mod inflection;
+pub mod numbers;
 pub mod radicals;
+pub mod variants;
 
-use itertools::Itertools;
-use std::iter;
+pub use numbers::{int_to_kanji_numeral, kanji_numeral_to_int, parse_counter};
+
+use serde::{Deserialize, Serialize};
+use std::{iter, ops::Range};
 use utils;
 
 const RADICALS: &[char] = &[
@@ -15,6 +19,19 @@ const RADICALS: &[char] = &[
     '⻖', '⺹', '⺣', '⺭', '⻂', '⺲',
 ];
 
+/// Small ヵ and ヶ as used in counters and place names (eg. 一ヶ月 or 霞ヶ関). They look like katakana
+/// but behave like the kanji 箇 within compounds
+const SMALL_KE: &[char] = &['\u{30F5}', '\u{30F6}'];
+
+/// Readings of [`SMALL_KE`] within kanji compounds
+pub(crate) const SMALL_KE_READINGS: &[&str] = &["か", "が", "こ"];
+
+/// Returns `true` if `c` is a small ヵ or ヶ
+#[inline]
+pub(crate) fn is_small_ke(c: char) -> bool {
+    SMALL_KE.contains(&c)
+}
+
 pub trait JapaneseExt {
     /// Returns true if self is of type ct
     fn is_of_type(&self, ct: CharType) -> bool;
@@ -28,12 +45,22 @@ pub trait JapaneseExt {
     /// Returns true if self is entirely written in kana
     fn is_kana(&self) -> bool;
 
-    /// Returns true if inp is entirely written with kanji
+    /// Returns true if inp is entirely written with kanji. Only kanji of the Basic Multilingual
+    /// Plane count: CJK Unified Ideographs, Extension A and the Compatibility Ideographs. Use
+    /// [`JapaneseExt::is_rare_kanji`] for kanji of the Supplementary Ideographic Plane
     fn is_kanji(&self) -> bool;
 
+    /// Returns true if inp is entirely written with rare kanji of CJK Extension B
+    /// (`U+20000..=U+2A6DF`), eg. 𠮟. Those aren't covered by [`JapaneseExt::is_kanji`]
+    fn is_rare_kanji(&self) -> bool;
+
     /// Returns true if inp has at least one kanji
     fn has_kanji(&self) -> bool;
 
+    /// Returns true if furigana should be calculated for self, which is the case as soon as it
+    /// contains a kanji. Used to skip furigana for kana-only text when furigana are requested
+    fn needs_furigana(&self) -> bool;
+
     /// Returns true if inp is build with kanji and kana only
     fn is_japanese(&self) -> bool;
 
@@ -57,6 +84,12 @@ pub trait JapaneseExt {
 
     fn to_hiragana(&self) -> String;
 
+    /// Converts hiragana and romaji within self to katakana
+    fn to_katakana(&self) -> String;
+
+    /// Converts kana within self to romaji
+    fn to_romaji(&self) -> String;
+
     fn has_roman_letter(&self) -> bool;
 
     fn is_roman_letter(&self) -> bool;
@@ -73,6 +106,12 @@ pub trait JapaneseExt {
     fn is_radical(&self) -> bool;
 
     fn is_particle(&self) -> bool;
+
+    /// Returns the amount of characters of each script within self
+    fn script_breakdown(&self) -> ScriptCounts;
+
+    /// Removes readings written in bracket notation from self, eg. 私[わたし] => 私
+    fn strip_furigana(&self) -> String;
 }
 
 impl JapaneseExt for char {
@@ -96,6 +135,16 @@ impl JapaneseExt for char {
         romaji::RomajiExt::to_hiragana(self.to_string().as_str())
     }
 
+    #[inline]
+    fn to_katakana(&self) -> String {
+        romaji::RomajiExt::to_katakana(self.to_string().as_str())
+    }
+
+    #[inline]
+    fn to_romaji(&self) -> String {
+        romaji::RomajiExt::to_romaji(self.to_string().as_str())
+    }
+
     #[inline]
     fn has_roman_letter(&self) -> bool {
         self.is_roman_letter()
@@ -105,7 +154,6 @@ impl JapaneseExt for char {
     fn is_roman_letter(&self) -> bool {
         (*self) >= '\u{FF01}' && (*self) <= '\u{FF5A}'
             || ((*self) >= '\u{2000}' && (*self) <= '\u{206F}')
-            || ((*self) >= '\u{20000}' && (*self) <= '\u{2A6DF}')
             || (*self) == '\u{2010}'
             || (*self) == '\u{2212}'
     }
@@ -116,8 +164,11 @@ impl JapaneseExt for char {
             || ((*self) >= '\u{4E00}' && (*self) <= '\u{9FFF}')
             || ((*self) >= '\u{F900}' && (*self) <= '\u{FAFF}')
             || ((*self) >= '\u{FF10}' && (*self) <= '\u{FF19}')
-            || ((*self) >= '\u{20000}' && (*self) <= '\u{2A6DF}')
-            || (*self) == '\u{29E8A}'
+    }
+
+    #[inline]
+    fn is_rare_kanji(&self) -> bool {
+        (*self) >= '\u{20000}' && (*self) <= '\u{2A6DF}'
     }
 
     #[inline]
@@ -129,6 +180,7 @@ impl JapaneseExt for char {
             || (*self) == '\u{002D}'
             || (*self) == '\u{3005}'
             || (*self) == '\u{00D7}'
+            || is_small_ke(*self)
     }
 
     #[inline]
@@ -143,7 +195,12 @@ impl JapaneseExt for char {
 
     #[inline]
     fn has_kanji(&self) -> bool {
-        self.is_kanji()
+        self.is_kanji() || self.is_rare_kanji()
+    }
+
+    #[inline]
+    fn needs_furigana(&self) -> bool {
+        self.has_kanji()
     }
 
     #[inline]
@@ -153,9 +210,9 @@ impl JapaneseExt for char {
 
     #[inline]
     fn get_text_type(&self) -> CharType {
-        if self.is_kana() {
+        if self.is_kana() && !is_small_ke(*self) {
             CharType::Kana
-        } else if self.is_kanji() || self.is_roman_letter() || self.is_symbol() {
+        } else if self.has_kanji() || self.is_roman_letter() || self.is_symbol() {
             CharType::Kanji
         } else {
             CharType::Other
@@ -164,7 +221,7 @@ impl JapaneseExt for char {
 
     #[inline]
     fn is_japanese(&self) -> bool {
-        self.is_kana() || self.is_kanji() || self.is_symbol() || self.is_roman_letter()
+        self.is_kana() || self.has_kanji() || self.is_symbol() || self.is_roman_letter()
     }
 
     #[inline]
@@ -174,7 +231,7 @@ impl JapaneseExt for char {
 
     #[inline]
     fn kanji_count(&self) -> usize {
-        if self.is_kanji() {
+        if self.has_kanji() {
             1
         } else {
             0
@@ -208,6 +265,18 @@ impl JapaneseExt for char {
             'を' | 'の' | 'に' | 'と' | 'が' | 'か' | 'は' | 'も' | 'で' | 'へ' | 'や'
         )
     }
+
+    #[inline]
+    fn script_breakdown(&self) -> ScriptCounts {
+        let mut counts = ScriptCounts::default();
+        counts.add(*self);
+        counts
+    }
+
+    #[inline]
+    fn strip_furigana(&self) -> String {
+        furigana::strip_furigana(&self.to_string())
+    }
 }
 
 impl JapaneseExt for str {
@@ -218,7 +287,7 @@ impl JapaneseExt for str {
 
     #[inline]
     fn get_text_type(&self) -> CharType {
-        if self.is_kanji() || self.is_symbol() {
+        if self.chars().all(|c| c.has_kanji()) || self.is_symbol() {
             CharType::Kanji
         } else if self.is_kana() {
             CharType::Kana
@@ -267,6 +336,11 @@ impl JapaneseExt for str {
         !self.chars().into_iter().any(|s| !s.is_kanji())
     }
 
+    #[inline]
+    fn is_rare_kanji(&self) -> bool {
+        !self.chars().into_iter().any(|s| !s.is_rare_kanji())
+    }
+
     #[inline]
     fn is_radical(&self) -> bool {
         !self.chars().into_iter().any(|s| !s.is_radical())
@@ -274,7 +348,12 @@ impl JapaneseExt for str {
 
     #[inline]
     fn has_kanji(&self) -> bool {
-        self.chars().into_iter().any(|s| s.is_kanji())
+        self.chars().into_iter().any(|s| s.has_kanji())
+    }
+
+    #[inline]
+    fn needs_furigana(&self) -> bool {
+        self.has_kanji()
     }
 
     #[inline]
@@ -282,7 +361,7 @@ impl JapaneseExt for str {
         let mut buf = [0; 16];
         !self.chars().into_iter().any(|c| {
             let s = c.encode_utf8(&mut buf);
-            !s.is_kana() && !s.is_kanji() && !s.is_symbol() && !s.is_roman_letter()
+            !s.is_kana() && !s.has_kanji() && !s.is_symbol() && !s.is_roman_letter()
         })
     }
 
@@ -291,13 +370,13 @@ impl JapaneseExt for str {
         let mut buf = [0; 16];
         self.chars().into_iter().any(|c| {
             let s = c.encode_utf8(&mut buf);
-            s.is_kana() || s.is_kanji() || s.is_symbol() || s.is_roman_letter()
+            s.is_kana() || s.has_kanji() || s.is_symbol() || s.is_roman_letter()
         })
     }
 
     #[inline]
     fn kanji_count(&self) -> usize {
-        self.chars().into_iter().filter(|i| i.is_kanji()).count()
+        self.chars().into_iter().filter(|i| i.has_kanji()).count()
     }
 
     #[inline]
@@ -307,8 +386,23 @@ impl JapaneseExt for str {
 
     #[inline]
     fn to_hiragana(&self) -> String {
+        // Nothing to convert, skip the romaji conversion
+        if self.is_hiragana() {
+            return self.to_owned();
+        }
+
         romaji::RomajiExt::to_hiragana(self)
     }
+
+    #[inline]
+    fn to_katakana(&self) -> String {
+        romaji::RomajiExt::to_katakana(self)
+    }
+
+    #[inline]
+    fn to_romaji(&self) -> String {
+        romaji::RomajiExt::to_romaji(self)
+    }
     #[inline]
 
     fn is_small_katakana(&self) -> bool {
@@ -329,9 +423,66 @@ impl JapaneseExt for str {
     fn is_particle(&self) -> bool {
         !self.chars().into_iter().any(|s| !s.is_particle())
     }
+
+    #[inline]
+    fn script_breakdown(&self) -> ScriptCounts {
+        let mut counts = ScriptCounts::default();
+        for c in self.chars() {
+            counts.add(c);
+        }
+        counts
+    }
+
+    #[inline]
+    fn strip_furigana(&self) -> String {
+        furigana::strip_furigana(self)
+    }
+}
+
+/// Returns `true` if `c` is a fullwidth latin letter, eg. Ｊ
+#[inline]
+fn is_fullwidth_letter(c: char) -> bool {
+    ('\u{FF01}'..='\u{FF5A}').contains(&c) && c.is_alphabetic()
+}
+
+/// Amount of characters of each script within a text
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ScriptCounts {
+    pub hiragana: usize,
+    pub katakana: usize,
+    pub kanji: usize,
+    pub romaji: usize,
+    pub symbol: usize,
+    pub other: usize,
+}
+
+impl ScriptCounts {
+    /// Counts `c` to the script it belongs to
+    fn add(&mut self, c: char) {
+        if c.is_hiragana() {
+            self.hiragana += 1;
+        } else if c.is_katakana() {
+            self.katakana += 1;
+        } else if c.is_kanji() || c.is_rare_kanji() {
+            self.kanji += 1;
+        } else if c.is_ascii_alphabetic() || is_fullwidth_letter(c) {
+            self.romaji += 1;
+        } else if c.is_symbol() {
+            self.symbol += 1;
+        } else {
+            self.other += 1;
+        }
+    }
+
+    /// Returns the total amount of counted characters
+    #[inline]
+    pub fn total(&self) -> usize {
+        self.hiragana + self.katakana + self.kanji + self.romaji + self.symbol + self.other
+    }
 }
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum CharType {
     Kana,
     Kanji,
@@ -340,27 +491,88 @@ pub enum CharType {
 
 /// Return all words of chartype ct
 pub fn all_words_with_ct(inp: &str, ct: CharType) -> Vec<String> {
-    let mut all: Vec<String> = Vec::new();
-    let mut curr = String::new();
-    let mut iter = inp.chars();
-    while let Some(c) = iter.next() {
-        if c.is_of_type(ct) {
-            curr.push(c);
-            continue;
-        } else {
-            if !curr.is_empty() {
-                all.push(curr.clone());
+    all_words_with_ct_indexed(inp, ct)
+        .into_iter()
+        .map(|(_, word)| word)
+        .collect()
+}
+
+/// Return all words of chartype ct along with the byte range they're located at within `inp`
+pub fn all_words_with_ct_indexed(inp: &str, ct: CharType) -> Vec<(Range<usize>, String)> {
+    let mut all: Vec<(Range<usize>, String)> = Vec::new();
+    let mut start: Option<usize> = None;
+
+    for (pos, c) in inp.char_indices() {
+        match (c.is_of_type(ct), start) {
+            (true, None) => start = Some(pos),
+            (false, Some(s)) => {
+                all.push((s..pos, inp[s..pos].to_string()));
+                start = None;
             }
-            curr.clear();
-            iter.take_while_ref(|i| !i.is_of_type(ct)).count();
+            _ => (),
         }
     }
-    if !curr.is_empty() {
-        all.push(curr);
+
+    if let Some(s) = start {
+        all.push((s..inp.len(), inp[s..].to_string()));
     }
+
     all
 }
 
+/// Splits `inp` into segments of characters with the same `CharType`. Returns each segment's
+/// `CharType` along with its byte range within `inp`
+pub fn split_by_char_type(inp: &str) -> Vec<(CharType, Range<usize>)> {
+    let mut segments: Vec<(CharType, Range<usize>)> = Vec::new();
+
+    for (pos, c) in inp.char_indices() {
+        let ct = c.get_text_type();
+        let end = pos + c.len_utf8();
+
+        match segments.last_mut() {
+            Some((last_ct, range)) if *last_ct == ct => range.end = end,
+            _ => segments.push((ct, pos..end)),
+        }
+    }
+
+    segments
+}
+
+/// Converts the romaji parts of text mixing romaji with japanese, eg. input partially converted
+/// by an IME, to hiragana. `tabeた` becomes `たべた`. Returns `None` if `inp` isn't such a text or
+/// a romaji part isn't entirely lowercase or can't fully be converted to kana. This keeps words
+/// like `DVDを見る` or `Jリーグ` untouched
+pub fn convert_mixed_romaji(inp: &str) -> Option<String> {
+    let segments = split_by_char_type(inp);
+
+    let has_japanese = segments.iter().any(|(ct, _)| *ct != CharType::Other);
+    let has_romaji = segments.iter().any(|(ct, _)| *ct == CharType::Other);
+    if !has_japanese || !has_romaji {
+        return None;
+    }
+
+    segments
+        .into_iter()
+        .map(|(ct, range)| {
+            let segment = &inp[range];
+            if ct != CharType::Other {
+                return Some(segment.to_owned());
+            }
+
+            if !segment.chars().all(|c| c.is_ascii_lowercase()) {
+                return None;
+            }
+
+            let kana = romaji::RomajiExt::to_hiragana(segment);
+            if kana.is_kana() {
+                Some(kana)
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
 /// Returns an iterator over all kanji / kana pairs
 pub fn text_parts<'a>(kanji: &'a str) -> impl Iterator<Item = &'a str> {
     let mut kanji_indices = kanji.char_indices().peekable();
@@ -378,6 +590,220 @@ pub fn text_parts<'a>(kanji: &'a str) -> impl Iterator<Item = &'a str> {
     })
 }
 
+/// Kana grouped by the vowel they end with. Used to resolve long vowel marks
+const VOWEL_ROWS: &[(char, &str)] = &[
+    ('あ', "あかがさざただなはばぱまやらわぁゃゎ"),
+    ('い', "いきぎしじちぢにひびぴみりぃ"),
+    ('う', "うくぐすずつづぬふぶぷむゆるぅゅゔ"),
+    ('え', "えけげせぜてでねへべぺめれぇ"),
+    ('お', "おこごそぞとどのほぼぽもよろをぉょ"),
+];
+
+/// Kana which can be voiced with a dakuten
+const VOICEABLE: &str = "かきくけこさしすせそたちつてとはひふへほ";
+
+/// Kana which can be voiced with a handakuten
+const SEMI_VOICEABLE: &str = "はひふへほ";
+
+/// Normalizes a kana reading so that readings only differing in their script (hiragana or
+/// katakana), the notation of long vowels (ー, おう or えい) or decomposed voicing marks are
+/// equal. Long vowels are always normalized to a repeated vowel, eg. コーヒー and こうひい both
+/// become こおひい
+pub fn normalize_reading(reading: &str) -> String {
+    let mut out: Vec<char> = Vec::with_capacity(reading.len());
+
+    let hiragana = reading.chars().flat_map(|c| {
+        if c.is_katakana() && c != 'ー' {
+            c.to_hiragana().chars().collect::<Vec<_>>()
+        } else {
+            vec![c]
+        }
+    });
+
+    for c in hiragana {
+        match c {
+            '\u{3099}' | '\u{309B}' | '\u{309A}' | '\u{309C}' => {
+                let handakuten = c == '\u{309A}' || c == '\u{309C}';
+                let composed = out.last().and_then(|last| {
+                    if handakuten {
+                        last.semi_voiced()
+                    } else {
+                        last.voiced()
+                    }
+                });
+                match composed {
+                    Some(composed) => *out.last_mut().unwrap() = composed,
+                    None => out.push(c),
+                }
+            }
+            'ー' => {
+                let vowel = out.last().and_then(|last| vowel_of(*last));
+                out.push(vowel.unwrap_or(c));
+            }
+            'う' if out.last().and_then(|last| vowel_of(*last)) == Some('お') => out.push('お'),
+            'い' if out.last().and_then(|last| vowel_of(*last)) == Some('え') => out.push('え'),
+            _ => out.push(c),
+        }
+    }
+
+    out.into_iter().collect()
+}
+
+/// Removes a trailing long vowel mark (ー) from `reading`, eg. コンピューター becomes コンピュータ.
+/// Loanwords are often spelled both ways
+#[inline]
+pub fn strip_trailing_long_mark(reading: &str) -> &str {
+    reading.strip_suffix('ー').unwrap_or(reading)
+}
+
+/// Replaces iteration marks (々, ゝ, ゞ, ヽ and ヾ) with the character they repeat, eg. 時々 becomes
+/// 時時 and いすゞ becomes いすず. Marks without a preceding character are kept
+pub fn expand_iteration_marks(text: &str) -> String {
+    let mut out: Vec<char> = Vec::with_capacity(text.len());
+
+    for c in text.chars() {
+        let prev = match out.last() {
+            Some(prev) => *prev,
+            None => {
+                out.push(c);
+                continue;
+            }
+        };
+
+        let expanded = match c {
+            '々' => prev,
+            'ゝ' | 'ヽ' => prev.devoiced().unwrap_or(prev),
+            'ゞ' | 'ヾ' => prev.voiced().unwrap_or(prev),
+            _ => c,
+        };
+        out.push(expanded);
+    }
+
+    out.into_iter().collect()
+}
+
+/// Returns true if `a` read as `a_reading` and `b` read as `b_reading` are different spellings of
+/// a kanji and kana word which only differ in their okurigana, eg. 表す and 表わす or 取り扱い and
+/// 取扱い. This is the case if both are read the same, have the same kanji and the kana following
+/// each kanji in one spelling end with the kana following it in the other one. Spellings read
+/// differently are different words, like 入る (はいる) and 入れる (いれる)
+pub fn is_okurigana_variant(a: &str, a_reading: &str, b: &str, b_reading: &str) -> bool {
+    if a == b || !a.has_kanji() || !b.has_kanji() || !a.has_kana() || !b.has_kana() {
+        return false;
+    }
+
+    if a_reading.to_hiragana() != b_reading.to_hiragana() {
+        return false;
+    }
+
+    let (a_prefix, a_kanji) = okurigana(a);
+    let (b_prefix, b_kanji) = okurigana(b);
+
+    a_prefix == b_prefix
+        && a_kanji.len() == b_kanji.len()
+        && a_kanji.iter().zip(b_kanji.iter()).all(|(a, b)| {
+            a.0 == b.0 && (a.1.ends_with(b.1.as_str()) || b.1.ends_with(a.1.as_str()))
+        })
+}
+
+/// Splits `text` into the text in front of its first kanji and each kanji along with the kana
+/// following it
+fn okurigana(text: &str) -> (String, Vec<(char, String)>) {
+    let mut prefix = String::new();
+    let mut kanji: Vec<(char, String)> = vec![];
+
+    for c in text.chars() {
+        match kanji.last_mut() {
+            _ if c.is_kanji() || c.is_rare_kanji() => kanji.push((c, String::new())),
+            Some(last) => last.1.push(c),
+            None => prefix.push(c),
+        }
+    }
+
+    (prefix, kanji)
+}
+
+/// Returns the vowel the hiragana `kana` ends with
+#[inline]
+fn vowel_of(kana: char) -> Option<char> {
+    VOWEL_ROWS
+        .iter()
+        .find(|(_, row)| row.contains(kana))
+        .map(|i| i.0)
+}
+
+/// Conversions between voiced and unvoiced kana
+pub trait KanaVoicing {
+    /// Returns the kana voiced with a dakuten, eg. か → が
+    fn voiced(&self) -> Option<char>;
+
+    /// Returns the kana voiced with a handakuten, eg. は → ぱ
+    fn semi_voiced(&self) -> Option<char>;
+
+    /// Returns the unvoiced form of a (semi) voiced kana, eg. が → か or ぱ → は
+    fn devoiced(&self) -> Option<char>;
+}
+
+impl KanaVoicing for char {
+    #[inline]
+    fn voiced(&self) -> Option<char> {
+        map_as_hiragana(*self, |c| {
+            if c == 'う' {
+                Some('ゔ')
+            } else if VOICEABLE.contains(c) {
+                char::from_u32(c as u32 + 1)
+            } else {
+                None
+            }
+        })
+    }
+
+    #[inline]
+    fn semi_voiced(&self) -> Option<char> {
+        map_as_hiragana(*self, |c| {
+            SEMI_VOICEABLE
+                .contains(c)
+                .then(|| char::from_u32(c as u32 + 2))
+                .flatten()
+        })
+    }
+
+    #[inline]
+    fn devoiced(&self) -> Option<char> {
+        map_as_hiragana(*self, |c| {
+            if c == 'ゔ' {
+                return Some('う');
+            }
+
+            let base = |offset: u32| char::from_u32((c as u32).checked_sub(offset)?);
+            base(1)
+                .filter(|i| VOICEABLE.contains(*i))
+                .or_else(|| base(2).filter(|i| SEMI_VOICEABLE.contains(*i)))
+        })
+    }
+}
+
+/// Offset between the hiragana and katakana unicode blocks
+const KATAKANA_OFFSET: u32 = 0x60;
+
+/// Applies `f` on the hiragana version of the kana `c` and returns the result in the script of
+/// `c`. Returns `None` if `c` isn't a kana which has a hiragana counterpart
+fn map_as_hiragana<F>(c: char, f: F) -> Option<char>
+where
+    F: Fn(char) -> Option<char>,
+{
+    if ('\u{3041}'..='\u{3096}').contains(&c) {
+        return f(c);
+    }
+
+    if !('\u{30A1}'..='\u{30F6}').contains(&c) {
+        return None;
+    }
+
+    let hiragana = char::from_u32(c as u32 - KATAKANA_OFFSET)?;
+    f(hiragana).and_then(|i| char::from_u32(i as u32 + KATAKANA_OFFSET))
+}
+
 /// Returns an iterator over kanji occurences having the reading [`reading`]
 pub fn has_reading<'a>(
     furigana: &'a str,
@@ -416,3 +842,237 @@ fn match_reading(comp: &str, comp_reading: &str, k_literal: char, reading: &str)
     // Impossible to check against other cases
     false
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_rare_kanji() {
+        assert!('漢'.is_kanji());
+        assert!(!'漢'.is_rare_kanji());
+
+        // U+20B9F, CJK Extension B
+        assert!('𠮟'.is_rare_kanji());
+        assert!(!'𠮟'.is_kanji());
+        assert!("𠮟".is_rare_kanji());
+        assert!(!"𠮟る".is_rare_kanji());
+        assert!(!'る'.is_rare_kanji());
+
+        assert!(!'𠮟'.is_roman_letter());
+        assert!('𠮟'.has_kanji());
+        assert!("𠮟る".has_kanji());
+        assert!("𠮟る".needs_furigana());
+        assert_eq!("𠮟".get_text_type(), CharType::Kanji);
+        assert_eq!("𠮟る".kanji_count(), 1);
+    }
+
+    #[test]
+    fn test_to_hiragana() {
+        assert_eq!("たべる".to_hiragana(), "たべる");
+        assert_eq!("".to_hiragana(), "");
+        assert_eq!("taberu".to_hiragana(), "たべる");
+        assert_eq!("タベル".to_hiragana(), "たべる");
+    }
+
+    #[test]
+    fn test_needs_furigana() {
+        assert!("食べる".needs_furigana());
+        assert!('食'.needs_furigana());
+        assert!(!"たべる".needs_furigana());
+        assert!(!"タベル".needs_furigana());
+    }
+
+    #[test]
+    fn test_all_words_with_ct() {
+        let kanji = |inp: &str| all_words_with_ct(inp, CharType::Kanji);
+
+        assert_eq!(kanji("漢A漢"), vec!["漢", "漢"]);
+        assert_eq!(kanji("漢AB漢字"), vec!["漢", "漢字"]);
+        assert_eq!(kanji("A漢A"), vec!["漢"]);
+        assert!(kanji("AB").is_empty());
+        assert!(kanji("").is_empty());
+    }
+
+    #[test]
+    fn test_small_ke_tokenization() {
+        let kanji = |inp: &str| all_words_with_ct(inp, CharType::Kanji);
+
+        assert_eq!(kanji("一ヶ月"), vec!["一ヶ月"]);
+        assert_eq!(kanji("霞ヶ関に行く"), vec!["霞ヶ関", "行"]);
+        assert_eq!(text_parts("一ヶ月").collect::<Vec<_>>(), vec!["一ヶ月"]);
+        assert_eq!(text_parts("ケーキ").collect::<Vec<_>>(), vec!["ケーキ"]);
+    }
+
+    #[test]
+    fn test_all_words_with_ct_indexed() {
+        let inp = "日本語を話すAB人";
+        let words = all_words_with_ct_indexed(inp, CharType::Kanji);
+        assert_eq!(
+            words,
+            vec![
+                (0..9, "日本語".to_string()),
+                (12..15, "話".to_string()),
+                (20..23, "人".to_string()),
+            ]
+        );
+
+        for (range, word) in words {
+            assert_eq!(&inp[range], word);
+        }
+    }
+
+    #[test]
+    fn test_split_by_char_type() {
+        assert_eq!(
+            split_by_char_type("漢字とカナ"),
+            vec![(CharType::Kanji, 0..6), (CharType::Kana, 6..15)]
+        );
+        assert_eq!(
+            split_by_char_type("食べる!"),
+            vec![
+                (CharType::Kanji, 0..3),
+                (CharType::Kana, 3..9),
+                (CharType::Other, 9..10)
+            ]
+        );
+        assert!(split_by_char_type("").is_empty());
+    }
+
+    #[test]
+    fn test_convert_mixed_romaji() {
+        assert_eq!(convert_mixed_romaji("tabeた"), Some("たべた".to_string()));
+        assert_eq!(convert_mixed_romaji("食beru"), Some("食べる".to_string()));
+        assert_eq!(convert_mixed_romaji("たべた"), None);
+        assert_eq!(convert_mixed_romaji("tabeta"), None);
+        assert_eq!(convert_mixed_romaji("日本 language"), None);
+        assert_eq!(convert_mixed_romaji("Nの"), None);
+        assert_eq!(convert_mixed_romaji("CDを"), None);
+        assert_eq!(convert_mixed_romaji("DVDを見る"), None);
+        assert_eq!(convert_mixed_romaji("Jリーグ"), None);
+    }
+
+    #[test]
+    fn test_normalize_reading() {
+        assert_eq!(normalize_reading("ラーメン"), "らあめん");
+        assert_eq!(normalize_reading("らーめん"), "らあめん");
+        assert_eq!(normalize_reading("コーヒー"), "こおひい");
+        assert_eq!(normalize_reading("か\u{3099}くせい"), "がくせえ");
+        assert_eq!(normalize_reading("は\u{309A}ん"), "ぱん");
+        assert_eq!(normalize_reading("がくせい"), "がくせえ");
+        assert_ne!(normalize_reading("はは"), normalize_reading("ばば"));
+    }
+
+    #[test]
+    fn test_normalize_long_vowels() {
+        assert_eq!(normalize_reading("コーヒー"), normalize_reading("こうひい"));
+        assert_eq!(normalize_reading("コーヒー"), normalize_reading("こおひい"));
+        assert_eq!(normalize_reading("せんせい"), normalize_reading("センセー"));
+        assert_ne!(normalize_reading("こうひい"), normalize_reading("くうひい"));
+        assert_eq!(normalize_reading("ゆう"), "ゆう");
+    }
+
+    #[test]
+    fn test_expand_iteration_marks() {
+        assert_eq!(expand_iteration_marks("時々"), "時時");
+        assert_eq!(expand_iteration_marks("いすゞ"), "いすず");
+        assert_eq!(expand_iteration_marks("こゝろ"), "こころ");
+        assert_eq!(expand_iteration_marks("バヽ"), "バハ");
+        assert_eq!(expand_iteration_marks("々"), "々");
+        assert_eq!(expand_iteration_marks("時時"), "時時");
+    }
+
+    #[test]
+    fn test_strip_trailing_long_mark() {
+        assert_eq!(strip_trailing_long_mark("コンピューター"), "コンピュータ");
+        assert_eq!(
+            strip_trailing_long_mark("コンピュータ"),
+            strip_trailing_long_mark("コンピューター")
+        );
+        assert_eq!(strip_trailing_long_mark("コーヒー"), "コーヒ");
+        assert_eq!(strip_trailing_long_mark("ー"), "");
+        assert_eq!(strip_trailing_long_mark("たべる"), "たべる");
+    }
+
+    #[test]
+    fn test_okurigana_variant() {
+        let variant = |a: &str, a_reading: &str, b: &str, b_reading: &str| {
+            is_okurigana_variant(a, a_reading, b, b_reading)
+        };
+
+        assert!(variant("表す", "あらわす", "表わす", "あらわす"));
+        assert!(variant("表わす", "あらわす", "表す", "あらわす"));
+        assert!(variant("手伝い", "てつだい", "手伝", "てつだい"));
+        assert!(variant("お手洗い", "おてあらい", "お手洗", "おてあらい"));
+
+        assert!(!variant("表す", "あらわす", "表す", "あらわす"));
+        assert!(!variant("表す", "あらわす", "現す", "あらわす"));
+        assert!(!variant("表", "おもて", "表す", "あらわす"));
+        assert!(!variant("お手洗い", "おてあらい", "手洗い", "てあらい"));
+        assert!(!variant("生える", "はえる", "生きる", "いきる"));
+        assert!(!variant("入る", "はいる", "入れる", "いれる"));
+        assert!(!variant("上る", "のぼる", "上がる", "あがる"));
+    }
+
+    #[test]
+    fn test_voicing() {
+        assert_eq!('か'.voiced(), Some('が'));
+        assert_eq!('が'.devoiced(), Some('か'));
+        assert_eq!('は'.voiced(), Some('ば'));
+        assert_eq!('は'.semi_voiced(), Some('ぱ'));
+        assert_eq!('ば'.devoiced(), Some('は'));
+        assert_eq!('ぱ'.devoiced(), Some('は'));
+        assert_eq!('う'.voiced(), Some('ゔ'));
+        assert_eq!('カ'.voiced(), Some('ガ'));
+        assert_eq!('ホ'.semi_voiced(), Some('ポ'));
+        assert_eq!('ポ'.devoiced(), Some('ホ'));
+    }
+
+    #[test]
+    fn test_voicing_none() {
+        assert_eq!('あ'.voiced(), None);
+        assert_eq!('か'.semi_voiced(), None);
+        assert_eq!('が'.voiced(), None);
+        assert_eq!('ぱ'.voiced(), None);
+        assert_eq!('か'.devoiced(), None);
+        assert_eq!('ん'.devoiced(), None);
+        assert_eq!('漢'.voiced(), None);
+        assert_eq!('a'.devoiced(), None);
+    }
+
+    #[test]
+    fn test_script_breakdown() {
+        let counts = "私はJotobaのアプリが好き。!".script_breakdown();
+        assert_eq!(
+            counts,
+            ScriptCounts {
+                hiragana: 4,
+                katakana: 3,
+                kanji: 2,
+                romaji: 6,
+                symbol: 1,
+                other: 1,
+            }
+        );
+        assert_eq!(counts.total(), 17);
+
+        let counts = "Ｊｏｔｏｂａ！…𠮟".script_breakdown();
+        assert_eq!(
+            counts,
+            ScriptCounts {
+                kanji: 1,
+                romaji: 6,
+                symbol: 1,
+                other: 1,
+                ..ScriptCounts::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_strip_furigana() {
+        assert_eq!("学生[がくせい]です".strip_furigana(), "学生です");
+        assert_eq!("ねこ".strip_furigana(), "ねこ");
+        assert_eq!('猫'.strip_furigana(), "猫");
+    }
+}
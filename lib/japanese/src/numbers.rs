@@ -0,0 +1,352 @@
+/// The script a number is written in
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NumberKind {
+    /// Half- or fullwidth arabic digits (3, ３)
+    Arabic,
+    /// Kanji numerals (三)
+    Kanji,
+    /// Native japanese numbers, used with the つ counter (みっつ)
+    Native,
+}
+
+/// A number parsed from japanese text
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Number {
+    pub value: u64,
+    pub kind: NumberKind,
+}
+
+impl Number {
+    #[inline]
+    pub fn new(value: u64, kind: NumberKind) -> Self {
+        Self { value, kind }
+    }
+}
+
+const KANJI_DIGITS: &[char] = &['〇', '一', '二', '三', '四', '五', '六', '七', '八', '九'];
+
+/// Native japanese numbers which are followed by the つ counter
+const NATIVE_NUMBERS: &[(&str, u64)] = &[
+    ("ひと", 1),
+    ("ふた", 2),
+    ("みっ", 3),
+    ("よっ", 4),
+    ("いつ", 5),
+    ("むっ", 6),
+    ("なな", 7),
+    ("やっ", 8),
+    ("ここの", 9),
+];
+
+/// Kanji markers for powers of ten within a group of four digits
+const SMALL_MARKERS: &[(char, i64)] = &[('十', 10), ('百', 100), ('千', 1000)];
+
+/// Kanji markers for groups of four digits
+const LARGE_MARKERS: &[(char, i64)] = &[
+    ('万', 10_000),
+    ('億', 100_000_000),
+    ('兆', 1_000_000_000_000),
+    ('京', 10_000_000_000_000_000),
+];
+
+#[inline]
+fn marker_value(markers: &[(char, i64)], c: char) -> Option<i64> {
+    markers.iter().find(|i| i.0 == c).map(|i| i.1)
+}
+
+/// Returns the value of a single kanji digit
+#[inline]
+fn kanji_digit(c: char) -> Option<u64> {
+    if c == '零' {
+        return Some(0);
+    }
+
+    KANJI_DIGITS.iter().position(|i| *i == c).map(|i| i as u64)
+}
+
+/// Returns the value of a single half- or fullwidth arabic digit
+#[inline]
+fn arabic_digit(c: char) -> Option<u64> {
+    match c {
+        '0'..='9' => Some(c as u64 - '0' as u64),
+        '０'..='９' => Some(c as u64 - '０' as u64),
+        _ => None,
+    }
+}
+
+/// Returns true if `c` is a kanji digit or a positional kanji marker like 十 or 万
+#[inline]
+fn is_kanji_numeral(c: char) -> bool {
+    kanji_digit(c).is_some()
+        || marker_value(SMALL_MARKERS, c).is_some()
+        || marker_value(LARGE_MARKERS, c).is_some()
+}
+
+/// Returns true if `c` is an arabic digit or a kanji numeral
+#[inline]
+pub fn is_numeral(c: char) -> bool {
+    arabic_digit(c).is_some() || is_kanji_numeral(c)
+}
+
+/// Converts a kanji numeral like 三, 十五 or 二千二十一 into an integer. Numbers written digit by
+/// digit (二〇二一) are supported as well, but can't be mixed with markers like 十 (一二十).
+/// Returns `None` if `s` isn't a valid kanji numeral
+pub fn kanji_numeral_to_int(s: &str) -> Option<i64> {
+    if s.is_empty() {
+        return None;
+    }
+
+    let mut total: i64 = 0;
+    // Value of the current group of four digits
+    let mut group: i64 = 0;
+    // Digits which haven't been multiplied by a marker yet
+    let mut digits: Option<i64> = None;
+    let mut last_small: Option<i64> = None;
+    let mut last_large: Option<i64> = None;
+    let digit_by_digit = s.chars().all(|c| kanji_digit(c).is_some());
+
+    for c in s.chars() {
+        if let Some(digit) = kanji_digit(c) {
+            // Numbers using markers have a single digit in front of each marker
+            if digits.is_some() && !digit_by_digit {
+                return None;
+            }
+
+            let digit = digit as i64;
+            digits = Some(digits.unwrap_or(0).checked_mul(10)?.checked_add(digit)?);
+        } else if let Some(marker) = marker_value(SMALL_MARKERS, c) {
+            // Markers within a group have to be in descending order
+            if last_small.map(|i| i <= marker).unwrap_or(false) {
+                return None;
+            }
+            last_small = Some(marker);
+
+            group = group.checked_add(digits.take().unwrap_or(1).checked_mul(marker)?)?;
+        } else if let Some(marker) = marker_value(LARGE_MARKERS, c) {
+            if last_large.map(|i| i <= marker).unwrap_or(false) {
+                return None;
+            }
+            // A large marker has to be preceded by a number within its group (eg. not 億万)
+            if last_large.is_some() && group == 0 && digits.is_none() {
+                return None;
+            }
+            last_large = Some(marker);
+            last_small = None;
+
+            let group_value = group.checked_add(digits.take().unwrap_or(0))?;
+            // A marker without a number in front of it (eg. 万) counts as one
+            let group_value = if group_value == 0 { 1 } else { group_value };
+            total = total.checked_add(group_value.checked_mul(marker)?)?;
+            group = 0;
+        } else {
+            return None;
+        }
+    }
+
+    total.checked_add(group)?.checked_add(digits.unwrap_or(0))
+}
+
+/// Converts `n` into its kanji numeral representation (eg. 2021 -> 二千二十一)
+pub fn int_to_kanji_numeral(n: i64) -> String {
+    if n == 0 {
+        return "零".to_string();
+    }
+
+    let mut out = String::new();
+    if n < 0 {
+        out.push_str("マイナス");
+    }
+
+    let mut rest = n.unsigned_abs();
+    let mut groups = vec![];
+    while rest > 0 {
+        groups.push(rest % 10_000);
+        rest /= 10_000;
+    }
+
+    for (pos, group) in groups.into_iter().enumerate().rev() {
+        if group == 0 {
+            continue;
+        }
+
+        push_group(&mut out, group);
+        if pos > 0 {
+            out.push(LARGE_MARKERS[pos - 1].0);
+        }
+    }
+
+    out
+}
+
+/// Pushes the kanji representation of `group` (< 10000) to `out`
+fn push_group(out: &mut String, group: u64) {
+    for (marker, value) in SMALL_MARKERS.iter().rev() {
+        let digit = (group / *value as u64) % 10;
+        if digit == 0 {
+            continue;
+        }
+
+        // 一 is omitted in front of 十, 百 and 千
+        if digit > 1 {
+            out.push(KANJI_DIGITS[digit as usize]);
+        }
+        out.push(*marker);
+    }
+
+    let ones = group % 10;
+    if ones > 0 {
+        out.push(KANJI_DIGITS[ones as usize]);
+    }
+}
+
+/// Parses a number written with either arabic digits or kanji numerals
+fn parse_number(num: &str) -> Option<Number> {
+    if num.chars().all(|c| arabic_digit(c).is_some()) {
+        let value = num.chars().try_fold(0u64, |acc, c| {
+            acc.checked_mul(10)?.checked_add(arabic_digit(c)?)
+        })?;
+        return Some(Number::new(value, NumberKind::Arabic));
+    }
+
+    if num.chars().all(is_kanji_numeral) {
+        let value = kanji_numeral_to_int(num)?;
+        return Some(Number::new(value as u64, NumberKind::Kanji));
+    }
+
+    None
+}
+
+/// Returns true if `num` ends with a marker like 十 or 万 which has no digit in front of it
+fn ends_with_bare_marker(num: &str) -> bool {
+    let mut chars = num.chars().rev();
+    let is_marker = |c: char| {
+        marker_value(SMALL_MARKERS, c).is_some() || marker_value(LARGE_MARKERS, c).is_some()
+    };
+
+    let ends_with_marker = chars.next().map(is_marker).unwrap_or(false);
+    let digit_before = chars.next().and_then(kanji_digit).is_some();
+    ends_with_marker && !digit_before
+}
+
+/// Parses a native japanese number followed by the つ counter (eg. ひとつ)
+fn parse_native(s: &str) -> Option<(Number, String)> {
+    NATIVE_NUMBERS.iter().find_map(|(prefix, value)| {
+        (s.strip_prefix(prefix)? == "つ")
+            .then(|| (Number::new(*value, NumberKind::Native), "つ".to_string()))
+    })
+}
+
+/// Splits a counter expression like 3人, 五冊 or ひとつ into its number and the counter. Returns
+/// `None` if `s` isn't a counter expression
+pub fn parse_counter(s: &str) -> Option<(Number, String)> {
+    if let Some(native) = parse_native(s) {
+        return Some(native);
+    }
+
+    let (counter_start, _) = s.char_indices().find(|(_, c)| !is_numeral(*c))?;
+    let (num, counter) = s.split_at(counter_start);
+    if num.is_empty() || !crate::JapaneseExt::is_japanese(counter) {
+        return None;
+    }
+
+    // Words like 千葉 start with a marker which isn't meant as a number
+    let counter_kanji = counter
+        .chars()
+        .next()
+        .map(|c| crate::JapaneseExt::is_kanji(&c))
+        .unwrap_or(false);
+    if counter_kanji && ends_with_bare_marker(num) {
+        return None;
+    }
+
+    Some((parse_number(num)?, counter.to_string()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_counter_arabic() {
+        assert_eq!(
+            parse_counter("3人"),
+            Some((Number::new(3, NumberKind::Arabic), "人".to_string()))
+        );
+        assert_eq!(
+            parse_counter("１２ヶ月"),
+            Some((Number::new(12, NumberKind::Arabic), "ヶ月".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_counter_kanji() {
+        assert_eq!(
+            parse_counter("五冊"),
+            Some((Number::new(5, NumberKind::Kanji), "冊".to_string()))
+        );
+        assert_eq!(
+            parse_counter("二十人"),
+            Some((Number::new(20, NumberKind::Kanji), "人".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_counter_native() {
+        assert_eq!(
+            parse_counter("ひとつ"),
+            Some((Number::new(1, NumberKind::Native), "つ".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_counter_invalid() {
+        assert_eq!(parse_counter("人"), None);
+        assert_eq!(parse_counter("3"), None);
+        assert_eq!(parse_counter("3kg"), None);
+        assert_eq!(parse_counter("3五冊"), None);
+        assert_eq!(parse_counter("千葉"), None);
+        assert_eq!(parse_counter("億万人"), None);
+    }
+
+    #[test]
+    fn test_kanji_numeral_to_int() {
+        assert_eq!(kanji_numeral_to_int("三"), Some(3));
+        assert_eq!(kanji_numeral_to_int("十"), Some(10));
+        assert_eq!(kanji_numeral_to_int("十五"), Some(15));
+        assert_eq!(kanji_numeral_to_int("二千二十一"), Some(2021));
+        assert_eq!(kanji_numeral_to_int("二〇二一"), Some(2021));
+        assert_eq!(kanji_numeral_to_int("三万五千"), Some(35_000));
+        assert_eq!(kanji_numeral_to_int("一億二千万"), Some(120_000_000));
+        assert_eq!(kanji_numeral_to_int("万"), Some(10_000));
+    }
+
+    #[test]
+    fn test_int_to_kanji_numeral() {
+        assert_eq!(int_to_kanji_numeral(0), "零");
+        assert_eq!(int_to_kanji_numeral(15), "十五");
+        assert_eq!(int_to_kanji_numeral(2021), "二千二十一");
+        assert_eq!(int_to_kanji_numeral(10_000), "一万");
+        assert_eq!(int_to_kanji_numeral(120_000_005), "一億二千万五");
+        assert_eq!(int_to_kanji_numeral(-30), "マイナス三十");
+    }
+
+    #[test]
+    fn test_kanji_numeral_roundtrip() {
+        for n in [1, 10, 99, 1001, 35_000, 2_000_021, i64::MAX] {
+            assert_eq!(kanji_numeral_to_int(&int_to_kanji_numeral(n)), Some(n));
+        }
+    }
+
+    #[test]
+    fn test_kanji_numeral_to_int_invalid() {
+        assert_eq!(kanji_numeral_to_int("猫"), None);
+        assert_eq!(kanji_numeral_to_int(""), None);
+        assert_eq!(kanji_numeral_to_int("十百"), None);
+        assert_eq!(kanji_numeral_to_int("一二十"), None);
+        assert_eq!(kanji_numeral_to_int("二〇千"), None);
+        assert_eq!(kanji_numeral_to_int("千二三"), None);
+        assert_eq!(kanji_numeral_to_int("三万二一"), None);
+        assert_eq!(kanji_numeral_to_int("億万"), None);
+        assert_eq!(kanji_numeral_to_int("一億万"), None);
+    }
+}
@@ -0,0 +1,39 @@
+/// Common words which are written in several ways. Each group contains all spellings of a word
+const SPELLING_VARIANTS: &[&[&str]] = &[
+    &["子供", "子ども", "こども"],
+    &["行う", "行なう", "おこなう"],
+    &["友達", "友だち", "ともだち"],
+    &["分かる", "解る", "判る", "わかる"],
+    &["出来る", "できる"],
+    &["下さい", "ください"],
+    &["沢山", "たくさん"],
+    &["綺麗", "奇麗", "きれい"],
+    &["有難う", "有り難う", "ありがとう"],
+    &["取り敢えず", "とりあえず"],
+    &["一生懸命", "一所懸命"],
+];
+
+/// Returns all other well-known spellings of `word`, eg. 子ども and こども for 子供
+pub fn spelling_variants(word: &str) -> impl Iterator<Item = &'static str> + '_ {
+    SPELLING_VARIANTS
+        .iter()
+        .filter(move |group| group.contains(&word))
+        .flat_map(|group| group.iter().copied())
+        .filter(move |variant| *variant != word)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_spelling_variants() {
+        let variants = spelling_variants("子供").collect::<Vec<_>>();
+        assert_eq!(variants, vec!["子ども", "こども"]);
+
+        let variants = spelling_variants("おこなう").collect::<Vec<_>>();
+        assert_eq!(variants, vec!["行う", "行なう"]);
+
+        assert_eq!(spelling_variants("食べる").count(), 0);
+    }
+}
@@ -1,10 +1,11 @@
 use std::path::Path;
 
+use japanese::{JapaneseExt, KanaVoicing};
 use serde::{Deserialize, Serialize};
 
 /// A Kanji representing structure containing all available information about a single kanji
 /// character.
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct Kanji {
     pub literal: char,
     pub grade: Option<u8>,
@@ -76,13 +77,33 @@ impl Reading {
             reading: reading.to_string(),
         }
     }
+
+    /// Guesses whether the reading is an on or kun reading by its script: katakana readings are
+    /// assumed to be on readings, all others kun readings. This is only a heuristic, on readings
+    /// may also be written in hiragana
+    #[inline]
+    pub fn guessed_reading_type(&self) -> ReadingType {
+        guess_reading_type(&self.reading)
+    }
+}
+
+/// Guesses the `ReadingType` of `reading` by its script. See [`Reading::guessed_reading_type`]
+fn guess_reading_type(reading: &str) -> ReadingType {
+    let reading = format_reading(reading);
+    if !reading.is_empty() && reading.is_katakana() {
+        ReadingType::Onyomi
+    } else {
+        ReadingType::Kunyomi
+    }
 }
 
 impl Kanji {
     /// Returns the `ReadingType` of `reading` within readings of a kanji
     pub fn get_reading_type(&self, reading: &str) -> Option<ReadingType> {
         let in_on = self.in_on_reading(reading);
-        let in_kun = self.in_kun_reading(reading);
+        // Kun readings are never written in katakana
+        let in_kun =
+            guess_reading_type(reading) == ReadingType::Kunyomi && self.in_kun_reading(reading);
 
         if in_on && !in_kun {
             return Some(ReadingType::Onyomi);
@@ -111,6 +132,38 @@ impl Kanji {
             .unwrap_or_default()
     }
 
+    /// Returns the `ReadingType` a kanji is read with, given the kana `segment` assigned to it
+    /// within a word (eg. furigana). Okurigana of kun readings are ignored and readings changed
+    /// by rendaku or gemination match as well, eg. がみ in 手紙. Returns `None` if the segment
+    /// matches none or both reading types
+    pub fn classify_reading(&self, segment: &str) -> Option<ReadingType> {
+        let segment = segment.to_hiragana();
+
+        let in_kun = self
+            .kunyomi
+            .as_ref()
+            .map(|i| {
+                i.iter()
+                    .any(|i| is_sound_changed(&literal_kun_reading(i).to_hiragana(), &segment))
+            })
+            .unwrap_or_default();
+
+        let in_on = self
+            .onyomi
+            .as_ref()
+            .map(|i| {
+                i.iter()
+                    .any(|i| is_sound_changed(&format_reading(i).to_hiragana(), &segment))
+            })
+            .unwrap_or_default();
+
+        match (in_kun, in_on) {
+            (true, false) => Some(ReadingType::Kunyomi),
+            (false, true) => Some(ReadingType::Onyomi),
+            _ => None,
+        }
+    }
+
     #[inline]
     pub fn get_literal_reading(&self, reading: &str) -> Option<String> {
         Some(match self.get_reading_type(reading)? {
@@ -119,6 +172,28 @@ impl Kanji {
         })
     }
 
+    /// Returns the reading of the kanji matching `reading`. Kun readings also match if written
+    /// without their okurigana separator, eg. `たべる` returns `た.べる` for 食
+    pub fn find_reading(&self, reading: &str) -> Option<&str> {
+        let kun = self.kunyomi.iter().flatten();
+        let on = self.onyomi.iter().flatten();
+
+        on.chain(kun.clone())
+            .find(|i| i.as_str() == reading)
+            .or_else(|| {
+                kun.filter(|i| i.contains('.'))
+                    .find(|i| format_reading(i) == reading)
+            })
+            .map(|i| i.as_str())
+    }
+
+    /// Returns `true` if the kanji is a jōyō kanji. Those are the kanji of the grades 1 to 6
+    /// taught in elementary school and grade 8 taught in secondary school
+    #[inline]
+    pub fn is_joyo(&self) -> bool {
+        matches!(self.grade, Some(1..=8))
+    }
+
     /// Returns true if kanji has a given reading
     #[inline]
     pub fn has_reading(&self, reading: &str) -> bool {
@@ -156,6 +231,29 @@ pub fn format_reading(reading: &str) -> String {
     reading.replace('-', "").replace('.', "")
 }
 
+/// Kana which become a small っ if the following kana gets geminated, eg. がく in 学校 (がっこう)
+const GEMINATING: &str = "きくちつ";
+
+/// Returns `true` if the hiragana `segment` equals `reading` or is `reading` changed by the sound
+/// changes within compounds: rendaku voices its first kana and gemination replaces its last kana
+/// by a small っ
+fn is_sound_changed(reading: &str, segment: &str) -> bool {
+    if reading.chars().count() != segment.chars().count() {
+        return false;
+    }
+
+    let last = reading.chars().count().saturating_sub(1);
+    reading
+        .chars()
+        .zip(segment.chars())
+        .enumerate()
+        .all(|(pos, (r, s))| {
+            r == s
+                || (pos == 0 && (r.voiced() == Some(s) || r.semi_voiced() == Some(s)))
+                || (pos == last && pos > 0 && s == 'っ' && GEMINATING.contains(r))
+        })
+}
+
 /// Returns the reading of a kanjis literal, given the kun reading
 #[inline]
 pub fn literal_kun_reading(kun: &str) -> String {
@@ -184,3 +282,90 @@ pub fn format_reading_with_literal(literal: char, reading: &str, r_type: Reading
         ReadingType::Onyomi => literal.to_string(),
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn kanji(literal: char, onyomi: &[&str], kunyomi: &[&str]) -> Kanji {
+        let to_vec = |i: &[&str]| Some(i.iter().map(|i| i.to_string()).collect());
+        Kanji {
+            literal,
+            onyomi: to_vec(onyomi),
+            kunyomi: to_vec(kunyomi),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_is_joyo() {
+        let mut kanji = kanji('音', &["オン"], &["おと"]);
+        assert!(!kanji.is_joyo());
+
+        kanji.grade = Some(1);
+        assert!(kanji.is_joyo());
+
+        // Jinmeiyō kanji
+        kanji.grade = Some(9);
+        assert!(!kanji.is_joyo());
+    }
+
+    #[test]
+    fn test_guessed_reading_type() {
+        let on = Reading::new("音", "カナ");
+        assert_eq!(on.guessed_reading_type(), ReadingType::Onyomi);
+
+        let kun = Reading::new("音", "かな");
+        assert_eq!(kun.guessed_reading_type(), ReadingType::Kunyomi);
+
+        let kun = Reading::new("食", "た.べる");
+        assert_eq!(kun.guessed_reading_type(), ReadingType::Kunyomi);
+    }
+
+    #[test]
+    fn test_classify_reading_on() {
+        let on = kanji('音', &["オン", "イン"], &["おと", "ね"]);
+        let gaku = kanji('楽', &["ガク", "ラク"], &["たの.しい", "たの.しむ"]);
+        assert_eq!(on.classify_reading("おん"), Some(ReadingType::Onyomi));
+        assert_eq!(gaku.classify_reading("がく"), Some(ReadingType::Onyomi));
+    }
+
+    #[test]
+    fn test_classify_reading_kun() {
+        let te = kanji('手', &["シュ", "ズ"], &["て", "て-", "-て", "た-"]);
+        let kami = kanji('紙', &["シ"], &["かみ"]);
+        assert_eq!(te.classify_reading("て"), Some(ReadingType::Kunyomi));
+        assert_eq!(kami.classify_reading("かみ"), Some(ReadingType::Kunyomi));
+        assert_eq!(kami.classify_reading("がく"), None);
+    }
+
+    #[test]
+    fn test_classify_reading_sound_change() {
+        let kami = kanji('紙', &["シ"], &["かみ"]);
+        let gaku = kanji('学', &["ガク"], &["まな.ぶ"]);
+        let hai = kanji('杯', &["ハイ"], &["さかずき"]);
+
+        // 手紙 (てがみ), 学校 (がっこう), 一杯 (いっぱい)
+        assert_eq!(kami.classify_reading("がみ"), Some(ReadingType::Kunyomi));
+        assert_eq!(gaku.classify_reading("がっ"), Some(ReadingType::Onyomi));
+        assert_eq!(hai.classify_reading("ぱい"), Some(ReadingType::Onyomi));
+
+        assert_eq!(kami.classify_reading("かっ"), None);
+        assert_eq!(kami.classify_reading("がが"), None);
+    }
+
+    #[test]
+    fn test_find_reading() {
+        let shoku = kanji(
+            '食',
+            &["ショク", "ジキ"],
+            &["く.う", "く.らう", "た.べる", "は.む"],
+        );
+        assert_eq!(shoku.find_reading("ショク"), Some("ショク"));
+        assert_eq!(shoku.find_reading("た.べる"), Some("た.べる"));
+        assert_eq!(shoku.find_reading("たべる"), Some("た.べる"));
+        assert_eq!(shoku.find_reading("くらう"), Some("く.らう"));
+        assert_eq!(shoku.find_reading("た"), None);
+        assert_eq!(shoku.find_reading("たべ"), None);
+    }
+}
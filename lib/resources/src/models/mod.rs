@@ -22,6 +22,11 @@ use self::{
 };
 use serde::{Deserialize, Serialize};
 
+/// Version of the layout of the stored `DictResources`. Has to be increased whenever a stored
+/// model changes, eg. a field of `Word` or `Sense` was added. Resources built with another version
+/// can't be read and have to be regenerated
+pub const FORMAT_VERSION: u32 = 2;
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DictResources {
     // words
@@ -38,16 +43,26 @@ pub struct DictResources {
 }
 
 impl DictResources {
-    /// Writes the resource storage into `out`
+    /// Writes the resource storage into `out`, prefixed with the [`FORMAT_VERSION`]
     #[inline]
     pub fn build<W: Write>(&self, out: &mut W) -> Result<(), bincode::Error> {
+        bincode::serialize_into(&mut *out, &FORMAT_VERSION)?;
         bincode::serialize_into(out, &self)
     }
 
     /// Builds a new `ResourceStorage` from a reader containing encoded data of all resources.
-    /// This file can be create by `build`
-    #[inline]
-    pub fn read<R: Read>(reader: R) -> Result<Self, bincode::Error> {
+    /// This file can be create by `build`. Fails if the data was built with another
+    /// [`FORMAT_VERSION`]
+    pub fn read<R: Read>(mut reader: R) -> Result<Self, bincode::Error> {
+        let version: u32 = bincode::deserialize_from(&mut reader)?;
+        if version != FORMAT_VERSION {
+            let msg = format!(
+                "Resources have format version {} but {} is required. Regenerate them",
+                version, FORMAT_VERSION
+            );
+            return Err(Box::new(bincode::ErrorKind::Custom(msg)));
+        }
+
         bincode::deserialize_from(reader)
     }
 }
@@ -100,3 +115,37 @@ fn load_rad_map<P: AsRef<Path>>(rad_map_file: P) -> Result<RadicalStorage, Box<d
 fn load_sentences<P: AsRef<Path>>(sentences: P) -> Result<SentenceStorage, Box<dyn Error>> {
     Ok(bincode::deserialize_from(File::open(sentences)?)?)
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn resources() -> DictResources {
+        DictResources {
+            words: vec![Word::default()],
+            word_jlpt: HashMap::new(),
+            kanji: vec![],
+            kanji_genki: HashMap::new(),
+            kanji_jlpt: HashMap::new(),
+            names: vec![],
+            radicals: vec![],
+        }
+    }
+
+    #[test]
+    fn test_format_version() {
+        let mut data = vec![];
+        resources().build(&mut data).unwrap();
+        let read = DictResources::read(data.as_slice()).unwrap();
+        assert_eq!(read.words, resources().words);
+
+        // Files without or with another version
+        let mut old = resources();
+        old.words.clear();
+        let old = bincode::serialize(&old).unwrap();
+        assert!(DictResources::read(old.as_slice()).is_err());
+
+        data[0] += 1;
+        assert!(DictResources::read(data.as_slice()).is_err());
+    }
+}
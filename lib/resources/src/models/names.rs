@@ -20,6 +20,9 @@ impl std::hash::Hash for Name {
 
 impl Eq for Name {}
 
+/// Separates the parts of foreign names written in katakana, eg. ジョン・スミス
+pub const NAME_PART_SEPARATOR: char = '・';
+
 impl Name {
     /// Return `true` if name is gendered
     pub fn is_gendered(&self) -> bool {
@@ -36,6 +39,23 @@ impl Name {
             .and_then(|i| i.iter().find(|i| i.is_gender()).copied())
     }
 
+    /// Returns `true` if name has the given `name_type`
+    #[inline]
+    pub fn has_name_type(&self, name_type: NameType) -> bool {
+        self.name_type
+            .as_ref()
+            .map(|i| i.contains(&name_type))
+            .unwrap_or(false)
+    }
+
+    /// Returns `true` if the names kana reading consists of multiple parts of which one equals
+    /// `part`, eg. `スミス` for ジョン・スミス
+    #[inline]
+    pub fn has_name_part(&self, part: &str) -> bool {
+        self.kana.contains(NAME_PART_SEPARATOR)
+            && self.kana.split(NAME_PART_SEPARATOR).any(|i| i == part)
+    }
+
     /// Returns `true` if name has at least one non-gender tag
     pub fn has_non_gender_tags(&self) -> bool {
         self.name_type
@@ -44,3 +64,51 @@ impl Name {
             .unwrap_or(false)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn name(sequence: u32, name_type: Option<Vec<NameType>>) -> Name {
+        Name {
+            sequence,
+            kana: String::new(),
+            kanji: None,
+            transcription: String::new(),
+            name_type,
+            xref: None,
+        }
+    }
+
+    #[test]
+    fn test_filter_place_names() {
+        let names = vec![
+            name(1, Some(vec![NameType::Surname])),
+            name(2, Some(vec![NameType::Place, NameType::Surname])),
+            name(3, None),
+            name(4, Some(vec![NameType::Place])),
+        ];
+
+        let places = names
+            .iter()
+            .filter(|i| i.has_name_type(NameType::Place))
+            .map(|i| i.sequence)
+            .collect::<Vec<_>>();
+        assert_eq!(places, vec![2, 4]);
+    }
+
+    #[test]
+    fn test_has_name_part() {
+        let mut smith = name(1, Some(vec![NameType::Person]));
+        smith.kana = "ジョン・スミス".to_string();
+
+        assert!(smith.has_name_part("スミス"));
+        assert!(smith.has_name_part("ジョン"));
+        assert!(!smith.has_name_part("スミ"));
+        assert!(!smith.has_name_part("ジョン・スミス"));
+
+        let mut tanaka = name(2, Some(vec![NameType::Surname]));
+        tanaka.kana = "たなか".to_string();
+        assert!(!tanaka.has_name_part("たなか"));
+    }
+}
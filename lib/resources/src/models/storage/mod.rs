@@ -25,6 +25,7 @@ use super::{
     DictResources,
 };
 use std::collections::HashMap;
+use utils::to_option;
 
 pub type WordStorage = IntMap<Word>;
 type NameStorage = IntMap<Name>;
@@ -54,6 +55,8 @@ pub struct DictionaryData {
 pub struct WordData {
     words: WordStorage,
     jlpt_word_map: HashMap<u8, Vec<u32>>,
+    /// Sequence ids of words by their normalized readings
+    normalized_readings: HashMap<String, Vec<u32>>,
     // genki_levels: HashMap<u8, Vec<u32>>,
 }
 
@@ -100,7 +103,31 @@ impl DictionaryData {
 
     /// Sets the word storage
     pub fn set_words(&mut self, words: Vec<Word>) {
-        self.word_data.words = build_words(words);
+        self.word_data.normalized_readings = build_normalized_readings(&words);
+        self.word_data.words = build_words(words, &self.kanji.kanji);
+    }
+
+    /// Sets the kun and on reading compounds of multiple kanji at once. Each item consists of
+    /// the kanji literal and the sequence ids of its kun and on compounds. Unknown kanji are
+    /// ignored. Returns the amount of updated kanji
+    pub fn update_links_bulk<I>(&mut self, links: I) -> usize
+    where
+        I: IntoIterator<Item = (char, Vec<u32>, Vec<u32>)>,
+    {
+        let mut updated = 0;
+
+        for (literal, kun_ids, on_ids) in links {
+            let kanji = match self.kanji.kanji.get_mut(&literal) {
+                Some(kanji) => kanji,
+                None => continue,
+            };
+
+            kanji.kun_dicts = to_option(kun_ids);
+            kanji.on_dicts = to_option(on_ids);
+            updated += 1;
+        }
+
+        updated
     }
 }
 
@@ -138,14 +165,16 @@ impl ResourceStorage {
         rad_map: RadicalStorage,
         sentences: SentenceStorage,
     ) -> Self {
-        let words = build_words(resources.words);
-        let names = build_names(resources.names);
+        let normalized_readings = build_normalized_readings(&resources.words);
         let kanji = build_kanji(resources.kanji);
+        let words = build_words(resources.words, &kanji);
+        let names = build_names(resources.names);
         let radicals = build_radicals(resources.radicals);
 
         let word_data = WordData {
             words,
             jlpt_word_map: resources.word_jlpt,
+            normalized_readings,
             // genki_levels: HashMap::new(),
         };
 
@@ -233,9 +262,38 @@ impl ResourceStorage {
     }
 }
 
-#[inline]
-pub fn build_words(words: Vec<Word>) -> WordStorage {
-    words.into_iter().map(|i| (i.sequence as u64, i)).collect()
+/// Builds the word storage. Readings of `kanji` are used to generate furigana for words whose
+/// main reading changed
+pub fn build_words(words: Vec<Word>, kanji: &KanjiStorage) -> WordStorage {
+    words
+        .into_iter()
+        .map(|mut i| {
+            i.order_readings_by_priority(|literal: String| {
+                let kanji = kanji.get(&literal.chars().next()?)?;
+                Some((kanji.kunyomi.clone(), kanji.onyomi.clone()))
+            });
+            (i.sequence as u64, i)
+        })
+        .collect()
+}
+
+/// Builds an index of normalized readings (see [`japanese::normalize_reading`]) to sequence ids
+fn build_normalized_readings(words: &[Word]) -> HashMap<String, Vec<u32>> {
+    let mut index: HashMap<String, Vec<u32>> = HashMap::new();
+
+    for word in words {
+        for reading in word.reading_iter(true) {
+            let seqs = index
+                .entry(japanese::normalize_reading(&reading.reading))
+                .or_default();
+
+            if !seqs.contains(&word.sequence) {
+                seqs.push(word.sequence);
+            }
+        }
+    }
+
+    index
 }
 
 #[inline]
@@ -252,3 +310,111 @@ fn build_kanji(kanji: Vec<Kanji>) -> KanjiStorage {
 fn build_radicals(radicals: Vec<DetailedRadical>) -> HashMap<char, DetailedRadical> {
     radicals.into_iter().map(|i| (i.literal, i)).collect()
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::words::{sense::XRef, Dict, Reading};
+
+    fn kanji(literal: char) -> Kanji {
+        Kanji {
+            literal,
+            ..Default::default()
+        }
+    }
+
+    fn word(sequence: u32, kana: &str) -> Word {
+        Word {
+            sequence,
+            reading: Reading {
+                kana: Dict {
+                    reading: kana.to_string(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_normalized_reading_search() {
+        let words = vec![word(1, "ラーメン"), word(2, "はは"), word(3, "ばば")];
+        let mut storage = ResourceStorage::default();
+        storage.dict_data.set_words(words.clone());
+
+        for query in ["ラーメン", "はは", "ばば"] {
+            let raw = words
+                .iter()
+                .filter(|i| i.reading.kana.reading == query)
+                .map(|i| i.sequence)
+                .collect::<Vec<_>>();
+
+            let normalized = storage
+                .words()
+                .by_normalized_reading(query)
+                .map(|i| i.sequence)
+                .collect::<Vec<_>>();
+
+            assert_eq!(raw, normalized);
+        }
+
+        let found = storage.words().by_normalized_reading("らあめん").count();
+        assert_eq!(found, 1);
+    }
+
+    #[test]
+    fn test_resolve_xref() {
+        let mut kami_paper = word(1, "かみ");
+        kami_paper.reading.kanji = Some(Dict {
+            reading: "紙".to_string(),
+            ..Default::default()
+        });
+        let mut kami_god = word(2, "かみ");
+        kami_god.reading.kanji = Some(Dict {
+            reading: "神".to_string(),
+            ..Default::default()
+        });
+        let mut shin = word(3, "しん");
+        shin.reading.kanji = Some(Dict {
+            reading: "神".to_string(),
+            ..Default::default()
+        });
+
+        let mut storage = ResourceStorage::default();
+        storage
+            .dict_data
+            .set_words(vec![kami_paper, kami_god, shin]);
+        let words = storage.words();
+
+        let resolve = |xref: &str| words.by_xref(&XRef::parse(xref)).map(|i| i.sequence);
+        assert_eq!(resolve("紙"), Some(1));
+        assert_eq!(resolve("神・しん・2"), Some(3));
+        assert_eq!(resolve("神・かみ"), Some(2));
+        assert_eq!(resolve("神・ばば"), None);
+        assert_eq!(XRef::parse("神・しん・2").sense, Some(2));
+    }
+
+    #[test]
+    fn test_update_links_bulk() {
+        let mut data = DictionaryData::default();
+        data.kanji.kanji = build_kanji(vec![kanji('手'), kanji('紙'), kanji('音')]);
+
+        let updated = data.update_links_bulk(vec![
+            ('手', vec![1, 2], vec![3]),
+            ('紙', vec![4], vec![]),
+            ('猫', vec![5], vec![6]),
+        ]);
+        assert_eq!(updated, 2);
+
+        let te = data.kanji.kanji.get(&'手').unwrap();
+        assert_eq!(te.kun_dicts, Some(vec![1, 2]));
+        assert_eq!(te.on_dicts, Some(vec![3]));
+
+        let kami = data.kanji.kanji.get(&'紙').unwrap();
+        assert_eq!(kami.kun_dicts, Some(vec![4]));
+        assert_eq!(kami.on_dicts, None);
+
+        assert_eq!(data.kanji.kanji.get(&'音').unwrap().kun_dicts, None);
+    }
+}
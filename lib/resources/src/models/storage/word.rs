@@ -1,4 +1,4 @@
-use crate::models::words::Word;
+use crate::models::words::{sense::XRef, Word};
 
 use super::ResourceStorage;
 
@@ -18,4 +18,29 @@ impl<'a> WordRetrieve<'a> {
     pub fn by_sequence(&self, seq_id: u32) -> Option<&'a Word> {
         self.storage.dict_data.word_data.words.get(seq_id as u64)
     }
+
+    /// Returns all words with a reading equal to `reading` after normalizing both (see
+    /// [`japanese::normalize_reading`])
+    pub fn by_normalized_reading(&self, reading: &str) -> impl Iterator<Item = &'a Word> + 'a {
+        let words = *self;
+        self.storage
+            .dict_data
+            .word_data
+            .normalized_readings
+            .get(&japanese::normalize_reading(reading))
+            .into_iter()
+            .flatten()
+            .filter_map(move |i| words.by_sequence(*i))
+    }
+
+    /// Returns the word referenced by `xref`
+    pub fn by_xref(&self, xref: &XRef) -> Option<&'a Word> {
+        self.by_normalized_reading(xref.text)
+            .filter(|word| word.reading_iter(true).any(|i| i.reading == xref.text))
+            .find(|word| {
+                xref.reading
+                    .map(|reading| word.reading.kana.reading == reading)
+                    .unwrap_or(true)
+            })
+    }
 }
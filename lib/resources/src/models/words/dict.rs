@@ -1,5 +1,6 @@
-use crate::parse::jmdict::{information::Information, priority::Priority};
+use crate::parse::jmdict::{information::Information, priority::Priority, EntryElement};
 use serde::{Deserialize, Serialize};
+use utils::to_option;
 
 /// A single dictionary entry representing a words reading
 #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, Hash, Eq)]
@@ -10,6 +11,23 @@ pub struct Dict {
     pub priorities: Option<Vec<Priority>>,
     pub reading_info: Option<Vec<Information>>,
     pub is_main: bool,
+    /// Kanji forms a kana reading exclusively applies to. Applies to all kanji forms if empty
+    pub restrictions: Vec<String>,
+}
+
+impl From<&EntryElement> for Dict {
+    #[inline]
+    fn from(element: &EntryElement) -> Self {
+        Self {
+            reading: element.value.clone(),
+            kanji: element.kanji,
+            no_kanji: element.no_true_reading,
+            priorities: to_option(element.priorities.clone()),
+            reading_info: to_option(element.reading_info.clone()),
+            is_main: false,
+            restrictions: element.restrictions.clone(),
+        }
+    }
 }
 
 impl Dict {
@@ -20,6 +38,21 @@ impl Dict {
         self.reading.chars().count()
     }
 
+    /// Returns `true` if the reading is an irregular form of the word
+    #[inline]
+    pub fn is_irregular(&self) -> bool {
+        self.reading_info
+            .as_ref()
+            .map(|i| i.iter().any(|i| i.is_irregular()))
+            .unwrap_or(false)
+    }
+
+    /// Returns `true` if the reading isn't restricted to kanji forms other than `kanji`
+    #[inline]
+    pub fn applies_to(&self, kanji: &str) -> bool {
+        self.restrictions.is_empty() || self.restrictions.iter().any(|i| i == kanji)
+    }
+
     /// Returns `true` if the reading has a length of zero
     #[inline]
     pub fn is_empty(&self) -> bool {
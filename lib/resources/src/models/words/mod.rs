@@ -9,6 +9,7 @@ pub use sense::{Gloss, Sense};
 use utils::to_option;
 
 use crate::parse::jmdict::{
+    field::Field,
     languages::Language,
     misc::Misc,
     part_of_speech::{PartOfSpeech, PosSimple},
@@ -16,7 +17,7 @@ use crate::parse::jmdict::{
 };
 use japanese::{
     accent::{AccentChar, Border},
-    furigana::{self, SentencePartRef},
+    furigana::{self, generate::RetrieveKanji, SentencePartRef},
     JapaneseExt,
 };
 use serde::{Deserialize, Serialize};
@@ -61,18 +62,102 @@ impl Word {
         self.jlpt_lvl
     }
 
+    /// Makes the most common readings the main readings of the word. Furigana of kanji words get
+    /// regenerated for the new main readings using the kanji readings provided by `retrieve`
+    pub fn order_readings_by_priority<R: RetrieveKanji>(&mut self, retrieve: R) {
+        if !self.reading.order_by_priority() {
+            return;
+        }
+
+        if let Some(kanji) = self.reading.kanji.as_ref() {
+            let furigana =
+                furigana::generate::checked(retrieve, &kanji.reading, &self.reading.kana.reading);
+            self.furigana = Some(furigana);
+        }
+    }
+
+    /// Merges senses having the same glosses into one sense. Senses with contradicting tags are
+    /// kept separate
+    pub fn merge_duplicate_senses(&mut self) {
+        let mut merged: Vec<Sense> = Vec::with_capacity(self.senses.len());
+
+        for sense in self.senses.drain(..) {
+            if !merged.iter_mut().any(|i| i.try_merge(&sense)) {
+                merged.push(sense);
+            }
+        }
+
+        self.senses = merged;
+    }
+
     /// Returns the reading of a word
     #[inline]
     pub fn get_reading(&self) -> &Dict {
         self.reading.get_reading()
     }
 
+    /// Returns the kanji spellings of the word which only differ from its main kanji reading in
+    /// their okurigana, eg. 表わす for 表す. Spellings the main kana reading doesn't apply to are
+    /// left out
+    pub fn okurigana_variants(&self) -> Vec<&str> {
+        let kanji = match self.reading.kanji.as_ref() {
+            Some(kanji) => kanji,
+            None => return vec![],
+        };
+
+        let kana = &self.reading.kana;
+        self.reading
+            .alternative
+            .iter()
+            .filter(|i| i.kanji && kana.applies_to(&i.reading))
+            .filter(|i| {
+                japanese::is_okurigana_variant(
+                    &kanji.reading,
+                    &kana.reading,
+                    &i.reading,
+                    &kana.reading,
+                )
+            })
+            .map(|i| i.reading.as_str())
+            .collect()
+    }
+
+    /// Returns `true` if `self` and `other` represent the same dictionary word, even if they have
+    /// been found through different readings. Words without a sequence id are compared by their
+    /// main reading
+    #[inline]
+    pub fn same_lemma(&self, other: &Word) -> bool {
+        if self.sequence != 0 && other.sequence != 0 {
+            return self.sequence == other.sequence;
+        }
+
+        self.get_reading().reading == other.get_reading().reading
+            && self.reading.kana.reading == other.reading.kana.reading
+    }
+
+    /// Returns a key identifying a word regardless of its loaded senses. Two words with the same
+    /// key represent the same dictionary entry
+    #[inline]
+    pub fn dedup_key(&self) -> (u32, &str) {
+        (self.sequence, &self.get_reading().reading)
+    }
+
     /// Return `true` if the word is a katakana word
     #[inline]
     pub fn is_katakana_word(&self) -> bool {
         self.reading.is_katakana()
     }
 
+    /// Returns the number of `sense` among the senses of its language within `self`, starting at
+    /// 1. Call this on the stored word to get numbers which don't depend on filtered out senses
+    pub fn sense_number(&self, sense: &Sense) -> Option<usize> {
+        self.senses
+            .iter()
+            .filter(|i| i.language == sense.language)
+            .position(|i| i.glosses == sense.glosses)
+            .map(|i| i + 1)
+    }
+
     /// Return all senses of a language
     #[inline]
     pub fn senses_by_lang(&self, language: Language) -> Option<Vec<Sense>> {
@@ -132,11 +217,26 @@ impl Word {
         })
     }
 
-    /// Returns a renderable vec of accents with kana characters
+    /// Returns a renderable vec of the most common accent with kana characters
+    #[inline]
     pub fn get_accents(&self) -> Option<Vec<AccentChar>> {
-        let accents_raw = self.accents.as_ref()?;
+        self.render_accent(*self.accents.as_ref()?.first()?)
+    }
+
+    /// Returns all accents of the word in a renderable format, the most common one first
+    pub fn accent_variants(&self) -> Vec<Vec<AccentChar>> {
+        self.accents
+            .iter()
+            .flatten()
+            .unique()
+            .filter_map(|drop| self.render_accent(*drop))
+            .collect()
+    }
+
+    /// Returns a renderable vec of the accent with a pitch drop after `drop` morae
+    fn render_accent(&self, drop: u8) -> Option<Vec<AccentChar>> {
         let kana = &self.reading.kana;
-        let accents = japanese::accent::calc_pitch(&kana.reading, accents_raw[0] as i32)?;
+        let accents = japanese::accent::calc_pitch(&kana.reading, drop as i32)?;
         let accent_iter = accents.iter().peekable().enumerate();
 
         let res = accent_iter
@@ -172,6 +272,44 @@ impl Word {
         Some(furigana::from_str(furi).collect::<Vec<_>>())
     }
 
+    /// Returns `true` if the word is usually written in kana, even though it has a kanji reading.
+    /// Like [`Word::has_misc`] this requires english glosses to be available
+    #[inline]
+    pub fn is_usually_kana(&self) -> bool {
+        self.has_misc(Misc::UsuallyWrittenInKana)
+    }
+
+    /// Returns the reading the word should be displayed with. This is the kana reading for words
+    /// usually written in kana and the main reading otherwise
+    #[inline]
+    pub fn primary_reading(&self) -> &Dict {
+        if self.is_usually_kana() {
+            &self.reading.kana
+        } else {
+            self.get_reading()
+        }
+    }
+
+    /// Returns the kanji reading of words displayed with their kana reading, to be shown next to
+    /// the primary reading
+    #[inline]
+    pub fn secondary_reading(&self) -> Option<&Dict> {
+        self.reading
+            .kanji
+            .as_ref()
+            .filter(|_| self.is_usually_kana())
+    }
+
+    /// Returns the furigana of the primary reading. Words displayed with their kana reading don't
+    /// need any
+    #[inline]
+    pub fn primary_furigana(&self) -> Option<Vec<SentencePartRef<'_>>> {
+        if self.is_usually_kana() {
+            return None;
+        }
+        self.get_furigana()
+    }
+
     /// Get alternative readings in a beautified, print-ready format
     #[inline]
     pub fn alt_readings_beautified(&self) -> String {
@@ -226,6 +364,57 @@ impl Word {
         self.senses.iter().filter_map(|i| i.misc).any(|i| i == misc)
     }
 
+    /// Returns `true` if word has at least one sense with the part of speech `pos`
+    #[inline]
+    pub fn has_detailed_pos(&self, pos: PartOfSpeech) -> bool {
+        self.senses.iter().any(|i| i.part_of_speech.contains(&pos))
+    }
+
+    /// Returns `true` if word has at least one sense in one of the given `fields`
+    #[inline]
+    pub fn has_field(&self, fields: &[Field]) -> bool {
+        self.senses
+            .iter()
+            .filter_map(|i| i.field)
+            .any(|i| fields.contains(&i))
+    }
+
+    /// Removes all senses which aren't in one of the given `fields`. Keeps all senses if none of
+    /// them is in `fields`, so a word never ends up without senses
+    pub fn retain_fields(&mut self, fields: &[Field]) {
+        if !self.has_field(fields) {
+            return;
+        }
+
+        self.senses
+            .retain(|i| i.field.map(|f| fields.contains(&f)).unwrap_or(false));
+    }
+
+    /// Removes all senses restricted to forms other than `form`. If `form` is no kanji form of the
+    /// word the main kanji form is used instead, same for readings. Keeps all senses if none of
+    /// them applies, so a word never ends up without senses
+    pub fn retain_form_senses(&mut self, form: &str) {
+        let kanji = self
+            .reading_iter(false)
+            .find(|i| i.kanji && i.reading == form)
+            .or_else(|| self.reading.kanji.as_ref())
+            .map(|i| i.reading.clone());
+
+        let kana = self
+            .reading_iter(true)
+            .find(|i| !i.kanji && i.reading == form)
+            .unwrap_or(&self.reading.kana)
+            .reading
+            .clone();
+
+        let applies = |sense: &Sense| sense.applies_to(kanji.as_deref(), &kana);
+        if !self.senses.iter().any(applies) {
+            return;
+        }
+
+        self.senses.retain(applies);
+    }
+
     /// Returns `true` if word has at least one of the provided part of speech
     #[inline]
     pub fn has_pos(&self, pos_filter: &[PosSimple]) -> bool {
@@ -247,6 +436,17 @@ impl Word {
             .any(|i| i.language == language || (allow_english && i.language == Language::English))
     }
 
+    /// Returns the language of the words first sense if the word has no sense in `language`, or
+    /// english if `allow_english` is `true`. This is the language a fallback to any available
+    /// language would show
+    pub fn fallback_language(&self, language: Language, allow_english: bool) -> Option<Language> {
+        if self.has_language(language, allow_english) {
+            return None;
+        }
+
+        self.senses.first().map(|i| i.language)
+    }
+
     /// Returns `true` if a word has collocations
     #[inline]
     pub fn has_collocations(&self) -> bool {
@@ -342,6 +542,61 @@ impl Reading {
     pub fn iter(&self, allow_kana: bool) -> ReadingIter<'_> {
         ReadingIter::new(self, allow_kana)
     }
+
+    /// Replaces uncommon main readings with common alternative ones and orders the alternative
+    /// readings to have common ones first. Readings are only replaced if the main kana reading
+    /// still applies to the main kanji reading afterwards. Returns `true` if a main reading has
+    /// been replaced
+    pub fn order_by_priority(&mut self) -> bool {
+        let mut changed = false;
+
+        if let Some(kanji) = self.kanji.as_mut() {
+            if kanji.priorities.is_none() {
+                let kana = &self.kana;
+                let pos =
+                    common_alternative(&self.alternative, true, |i| kana.applies_to(&i.reading));
+                if let Some(pos) = pos {
+                    swap_main(kanji, &mut self.alternative[pos]);
+                    changed = true;
+                }
+            }
+        }
+
+        if self.kana.priorities.is_none() {
+            let kanji = self.kanji.as_ref().map(|i| i.reading.as_str());
+            let pos = common_alternative(&self.alternative, false, |i| {
+                kanji.map_or(true, |kanji| i.applies_to(kanji))
+            });
+            if let Some(pos) = pos {
+                swap_main(&mut self.kana, &mut self.alternative[pos]);
+                changed = true;
+            }
+        }
+
+        self.alternative.sort_by_key(|i| i.priorities.is_none());
+
+        changed
+    }
+}
+
+/// Returns the position of the first common alternative reading which is either written in kanji
+/// or in kana and for which `valid` returns `true`
+#[inline]
+fn common_alternative<F>(alternative: &[Dict], kanji: bool, valid: F) -> Option<usize>
+where
+    F: Fn(&Dict) -> bool,
+{
+    alternative
+        .iter()
+        .position(|i| i.kanji == kanji && i.priorities.is_some() && valid(i))
+}
+
+/// Makes `alternative` the main reading instead of `main`
+#[inline]
+fn swap_main(main: &mut Dict, alternative: &mut Dict) {
+    std::mem::swap(main, alternative);
+    main.is_main = true;
+    alternative.is_main = false;
 }
 
 pub struct ReadingIter<'a> {
@@ -389,10 +644,460 @@ pub fn filter_languages<'a, I: 'a + Iterator<Item = &'a mut Word>>(
     iter: I,
     language: Language,
     show_english: bool,
+) {
+    filter_languages_with(iter, language, show_english, false);
+}
+
+/// Like [`filter_languages`] but keeps japanese (monolingual) senses if `prefer_monolingual` is
+/// set. Those get ordered right after the senses of `language` and in front of english ones
+pub fn filter_languages_with<'a, I: 'a + Iterator<Item = &'a mut Word>>(
+    iter: I,
+    language: Language,
+    show_english: bool,
+    prefer_monolingual: bool,
 ) {
     for word in iter {
         word.senses.retain(|j| {
-            j.language == language || (j.language == Language::English && show_english)
+            j.language == language
+                || (j.language == Language::English && show_english)
+                || (j.language == Language::Japanese && prefer_monolingual)
+        });
+
+        if prefer_monolingual {
+            word.senses.sort_by_key(|j| {
+                if j.language == language {
+                    0
+                } else if j.language == Language::Japanese {
+                    1
+                } else {
+                    2
+                }
+            });
+        }
+    }
+}
+
+/// Like [`filter_languages_with`] but keeps the senses of the [`Word::fallback_language`] for words
+/// which would end up without any sense otherwise
+pub fn filter_languages_fallback<'a, I: 'a + Iterator<Item = &'a mut Word>>(
+    iter: I,
+    language: Language,
+    show_english: bool,
+    prefer_monolingual: bool,
+) {
+    for word in iter {
+        let fallback = word.fallback_language(language, show_english);
+        let word = std::iter::once(word);
+        match fallback {
+            Some(fallback) => filter_languages_with(word, fallback, false, false),
+            None => filter_languages_with(word, language, show_english, prefer_monolingual),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn sense(language: Language, gloss: &str) -> Sense {
+        Sense {
+            language,
+            glosses: vec![Gloss {
+                gloss: gloss.to_string(),
+                g_type: None,
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn word() -> Word {
+        Word {
+            senses: vec![
+                sense(Language::English, "to eat"),
+                sense(Language::Japanese, "食物を口に入れ、かんで飲み込む"),
+                sense(Language::German, "essen"),
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_usually_kana_primary_reading() {
+        let mut suru = word();
+        suru.reading.kana.reading = "する".to_string();
+        suru.reading.kanji = Some(Dict {
+            reading: "為る".to_string(),
+            kanji: true,
+            ..Default::default()
         });
+        suru.furigana = Some("[為|す]る".to_string());
+        assert_eq!(suru.primary_reading().reading, "為る");
+        assert_eq!(suru.secondary_reading(), None);
+        assert!(suru.primary_furigana().is_some());
+
+        suru.senses[0].misc = Some(Misc::UsuallyWrittenInKana);
+        assert!(suru.is_usually_kana());
+        assert_eq!(suru.primary_reading().reading, "する");
+        assert_eq!(suru.secondary_reading().unwrap().reading, "為る");
+        assert!(suru.primary_furigana().is_none());
+    }
+
+    #[test]
+    fn test_sense_number() {
+        let mut stored = word();
+        stored.senses.push(sense(Language::English, "to live on"));
+        stored.senses.push(sense(Language::German, "fressen"));
+
+        let mut filtered = stored.clone();
+        filtered.senses.retain(|i| i.language == Language::German);
+        filtered.senses.push(stored.senses[3].clone());
+
+        let numbers = filtered
+            .senses
+            .iter()
+            .map(|i| stored.sense_number(i))
+            .collect::<Vec<_>>();
+        assert_eq!(numbers, vec![Some(1), Some(2), Some(2)]);
+
+        let unknown = sense(Language::English, "to drink");
+        assert_eq!(stored.sense_number(&unknown), None);
+    }
+
+    #[test]
+    fn test_retain_fields() {
+        let mut medical = sense(Language::English, "sign of a disease");
+        medical.field = Some(Field::Medicine);
+        let mut word = Word {
+            senses: vec![sense(Language::English, "sign"), medical.clone()],
+            ..Default::default()
+        };
+
+        word.retain_fields(&[Field::Computing]);
+        assert_eq!(word.senses.len(), 2);
+
+        word.retain_fields(&[Field::Medicine]);
+        assert_eq!(word.senses, vec![medical]);
+    }
+
+    #[test]
+    fn test_sense_from_entry_sense() {
+        use crate::parse::jmdict::{EntrySense, GlossValue};
+
+        let gloss = |language: Language, value: &str| GlossValue {
+            language,
+            g_type: None,
+            value: value.to_string(),
+        };
+        let entry_sense = EntrySense {
+            glosses: vec![
+                gloss(Language::English, "a little"),
+                gloss(Language::German, "ein wenig"),
+                gloss(Language::English, "a moment"),
+            ],
+            misc: Some(Misc::UsuallyWrittenInKana),
+            restrictions: vec!["一寸".to_string()],
+            ..Default::default()
+        };
+
+        let senses = Sense::from_entry_sense(&entry_sense);
+        assert_eq!(senses.len(), 2);
+        assert_eq!(senses[0].language, Language::English);
+        assert_eq!(senses[0].get_glosses(), "a little; a moment");
+        assert_eq!(senses[1].language, Language::German);
+        assert_eq!(senses[1].get_glosses(), "ein wenig");
+        for sense in senses.iter() {
+            assert_eq!(sense.misc, Some(Misc::UsuallyWrittenInKana));
+            assert_eq!(sense.restrictions, vec!["一寸".to_string()]);
+            assert!(!sense.applies_to(Some("鳥渡"), "ちょっと"));
+        }
+    }
+
+    #[test]
+    fn test_retain_form_senses() {
+        let mut restricted = sense(Language::English, "one sun");
+        restricted.restrictions = vec!["一寸".to_string()];
+        let word = Word {
+            reading: Reading {
+                kana: Dict {
+                    reading: "ちょっと".to_string(),
+                    ..Default::default()
+                },
+                kanji: Some(Dict {
+                    reading: "一寸".to_string(),
+                    kanji: true,
+                    ..Default::default()
+                }),
+                alternative: vec![Dict {
+                    reading: "鳥渡".to_string(),
+                    kanji: true,
+                    ..Default::default()
+                }],
+            },
+            senses: vec![sense(Language::English, "a little"), restricted.clone()],
+            ..Default::default()
+        };
+
+        let glosses = |form: &str| {
+            let mut word = word.clone();
+            word.retain_form_senses(form);
+            word.senses
+                .iter()
+                .map(|i| i.get_glosses())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(glosses("一寸"), vec!["a little", "one sun"]);
+        assert_eq!(glosses("鳥渡"), vec!["a little"]);
+        // Readings use the main kanji form
+        assert_eq!(glosses("ちょっと"), vec!["a little", "one sun"]);
+
+        let mut only_restricted = word.clone();
+        only_restricted.senses = vec![restricted];
+        only_restricted.retain_form_senses("鳥渡");
+        assert_eq!(only_restricted.senses.len(), 1);
+    }
+
+    #[test]
+    fn test_accent_variants() {
+        let mut word = Word {
+            reading: Reading {
+                kana: Dict {
+                    reading: "なま".to_string(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            accents: Some(vec![1, 0, 1]),
+            ..Default::default()
+        };
+
+        let variants = word.accent_variants();
+        assert_eq!(variants.len(), 2);
+        assert_eq!(Some(variants[0].clone()), word.get_accents());
+        // Atamadaka (high first mora) comes first, heiban second
+        assert_eq!(variants[0][0].borders[0], Border::Top);
+        assert_eq!(variants[1][0].borders[0], Border::Bottom);
+
+        word.accents = None;
+        assert!(word.accent_variants().is_empty());
+        assert_eq!(word.get_accents(), None);
+    }
+
+    #[test]
+    fn test_filter_languages_monolingual() {
+        let mut words = vec![word()];
+        filter_languages_with(words.iter_mut(), Language::German, true, true);
+
+        let languages = words[0]
+            .senses
+            .iter()
+            .map(|i| i.language)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            languages,
+            vec![Language::German, Language::Japanese, Language::English]
+        );
+    }
+
+    #[test]
+    fn test_filter_languages_no_monolingual() {
+        let mut words = vec![word()];
+        filter_languages(words.iter_mut(), Language::German, true);
+
+        assert!(!words[0]
+            .senses
+            .iter()
+            .any(|i| i.language == Language::Japanese));
+    }
+
+    #[test]
+    fn test_filter_languages_fallback() {
+        let dutch = || Word {
+            senses: vec![sense(Language::Dutch, "eten")],
+            ..Default::default()
+        };
+
+        let mut words = vec![dutch()];
+        filter_languages(words.iter_mut(), Language::French, true);
+        assert!(words[0].senses.is_empty());
+
+        let mut words = vec![dutch(), word()];
+        filter_languages_fallback(words.iter_mut(), Language::French, true, false);
+        assert_eq!(words[0].senses, dutch().senses);
+        assert_eq!(
+            words[0].fallback_language(Language::French, true),
+            Some(Language::Dutch)
+        );
+
+        assert_eq!(words[1].senses, vec![sense(Language::English, "to eat")]);
+        assert_eq!(words[1].fallback_language(Language::French, true), None);
+    }
+
+    #[test]
+    fn test_okurigana_variants() {
+        let dict = |reading: &str, kanji: bool| Dict {
+            reading: reading.to_string(),
+            kanji,
+            ..Default::default()
+        };
+        let mut word = Word {
+            reading: Reading {
+                kana: dict("あらわす", false),
+                kanji: Some(dict("表す", true)),
+                alternative: vec![dict("表わす", true), dict("現す", true)],
+            },
+            ..Default::default()
+        };
+        assert_eq!(word.okurigana_variants(), vec!["表わす"]);
+
+        word.reading.kana.restrictions = vec!["表す".to_string()];
+        assert!(word.okurigana_variants().is_empty());
+    }
+
+    #[test]
+    fn test_merge_duplicate_senses() {
+        let mut first = sense(Language::English, "to eat");
+        first.part_of_speech = vec![PartOfSpeech::Adverb];
+        let mut second = sense(Language::English, "to eat");
+        second.part_of_speech = vec![PartOfSpeech::Expr];
+        second.information = Some("formal".to_string());
+
+        let mut word = Word {
+            senses: vec![first, sense(Language::English, "to drink"), second],
+            ..Default::default()
+        };
+        word.merge_duplicate_senses();
+
+        assert_eq!(word.senses.len(), 2);
+        assert_eq!(
+            word.senses[0].part_of_speech,
+            vec![PartOfSpeech::Adverb, PartOfSpeech::Expr]
+        );
+        assert_eq!(word.senses[0].information.as_deref(), Some("formal"));
+        assert_eq!(word.senses[1].glosses[0].gloss, "to drink");
+    }
+
+    #[test]
+    fn test_merge_duplicate_senses_distinct() {
+        let mut first = sense(Language::English, "to eat");
+        first.information = Some("formal".to_string());
+        let mut second = sense(Language::English, "to eat");
+        second.information = Some("colloquial".to_string());
+
+        let mut word = Word {
+            senses: vec![first, second, sense(Language::German, "to eat")],
+            ..Default::default()
+        };
+        word.merge_duplicate_senses();
+
+        assert_eq!(word.senses.len(), 3);
+    }
+
+    fn retrieve(kanji: String) -> Option<(Option<Vec<String>>, Option<Vec<String>>)> {
+        let readings = |i: &[&str]| Some(i.iter().map(|i| i.to_string()).collect());
+        match kanji.as_str() {
+            "日" => Some((readings(&["ひ", "か"]), readings(&["ニチ", "ジツ"]))),
+            "本" => Some((readings(&["もと"]), readings(&["ホン"]))),
+            _ => None,
+        }
+    }
+
+    fn dict(reading: &str, kanji: bool, common: bool) -> Dict {
+        Dict {
+            reading: reading.to_string(),
+            kanji,
+            priorities: common.then(|| vec![Priority::Ichi(1)]),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_order_readings_by_priority() {
+        let mut word = Word {
+            reading: Reading {
+                kana: dict("にっぽん", false, false),
+                kanji: Some(dict("日本", true, true)),
+                alternative: vec![dict("ニッポン", false, false), dict("にほん", false, true)],
+            },
+            furigana: Some("[日本|にっ|ぽん]".to_string()),
+            ..Default::default()
+        };
+        word.order_readings_by_priority(retrieve);
+
+        assert_eq!(word.reading.kana.reading, "にほん");
+        assert!(word.reading.kana.is_main);
+        assert_eq!(word.reading.kanji.as_ref().unwrap().reading, "日本");
+        assert_eq!(word.reading.alternative[0].reading, "ニッポン");
+        assert_eq!(word.reading.alternative[1].reading, "にっぽん");
+
+        // Furigana got regenerated for the new kana reading
+        let furigana = word.get_furigana().unwrap();
+        assert_eq!(furigana.iter().map(|i| i.kana).join(""), "にほん");
+    }
+
+    #[test]
+    fn test_order_readings_by_priority_restricted() {
+        // The common kana reading is restricted to another kanji form
+        let mut restricted = dict("ひとこと", false, true);
+        restricted.restrictions = vec!["一言".to_string()];
+        let mut word = Word {
+            reading: Reading {
+                kana: dict("いちげん", false, false),
+                kanji: Some(dict("一言", true, false)),
+                alternative: vec![dict("壱言", true, true), restricted],
+            },
+            furigana: Some("[一言|いち|げん]".to_string()),
+            ..Default::default()
+        };
+
+        // いちげん only applies to 一言 and ひとこと only to 壱言, so no reading can be replaced
+        let mut exclusive = word.clone();
+        exclusive.reading.kana.restrictions = vec!["一言".to_string()];
+        exclusive.reading.alternative[1].restrictions = vec!["壱言".to_string()];
+        exclusive.order_readings_by_priority(retrieve);
+        assert_eq!(exclusive.reading.kanji.as_ref().unwrap().reading, "一言");
+        assert_eq!(exclusive.reading.kana.reading, "いちげん");
+
+        // 壱言 replaces 一言 but ひとこと can't be used for it
+        word.order_readings_by_priority(retrieve);
+        assert_eq!(word.reading.kanji.as_ref().unwrap().reading, "壱言");
+        assert_eq!(word.reading.kana.reading, "いちげん");
+    }
+
+    #[test]
+    fn test_order_readings_by_priority_unchanged() {
+        let mut word = Word {
+            reading: Reading {
+                kana: dict("にほん", false, true),
+                kanji: Some(dict("日本", true, true)),
+                alternative: vec![
+                    dict("にっぽん", false, false),
+                    dict("ニッポン", false, true),
+                ],
+            },
+            furigana: Some("[日本|に|ほん]".to_string()),
+            ..Default::default()
+        };
+        word.order_readings_by_priority(retrieve);
+
+        assert_eq!(word.reading.kana.reading, "にほん");
+        assert_eq!(word.reading.alternative[0].reading, "ニッポン");
+        assert!(word.furigana.is_some());
+    }
+
+    #[test]
+    fn test_same_lemma() {
+        let word = |sequence: u32, kana: &str| Word {
+            sequence,
+            reading: Reading {
+                kana: dict(kana, false, false),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        assert!(word(1, "にほん").same_lemma(&word(1, "にっぽん")));
+        assert!(!word(1, "にほん").same_lemma(&word(2, "にほん")));
+        assert!(word(0, "にほん").same_lemma(&word(0, "にほん")));
+        assert!(!word(0, "にほん").same_lemma(&word(0, "にっぽん")));
     }
 }
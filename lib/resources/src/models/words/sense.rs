@@ -5,7 +5,7 @@ use crate::parse::jmdict::{
     languages::Language,
     misc::Misc,
     part_of_speech::{PartOfSpeech, PosSimple},
-    Gairaigo,
+    EntrySense, Gairaigo,
 };
 use itertools::Itertools;
 use localization::{language::Language as LocLanguage, traits::Translatable, TranslationDict};
@@ -26,6 +26,8 @@ pub struct Sense {
     pub language: Language,
     pub example_sentence: Option<u32>,
     pub gairaigo: Option<Gairaigo>,
+    /// Kanji forms and readings the sense exclusively applies to. Applies to all forms if empty
+    pub restrictions: Vec<String>,
 }
 
 impl Eq for Sense {}
@@ -39,6 +41,41 @@ pub struct Gloss {
 }
 
 impl Sense {
+    /// Creates one `Sense` for each language of the glosses of a parsed jmdict sense, in the order
+    /// the languages first occur. All of them keep the tags and restrictions of `sense`
+    pub fn from_entry_sense(sense: &EntrySense) -> Vec<Sense> {
+        let languages = sense.glosses.iter().map(|i| i.language).unique();
+
+        languages
+            .map(|language| {
+                let glosses = sense
+                    .glosses
+                    .iter()
+                    .filter(|i| i.language == language)
+                    .map(|i| Gloss {
+                        gloss: i.value.clone(),
+                        g_type: i.g_type,
+                    })
+                    .collect();
+
+                Sense {
+                    misc: sense.misc,
+                    field: sense.field,
+                    dialect: sense.dialect,
+                    glosses,
+                    xref: sense.xref.clone(),
+                    antonym: sense.antonym.clone(),
+                    information: sense.information.clone(),
+                    part_of_speech: sense.part_of_speech.clone(),
+                    language,
+                    example_sentence: sense.example_sentence,
+                    gairaigo: sense.gairaigo.clone(),
+                    restrictions: sense.restrictions.clone(),
+                }
+            })
+            .collect()
+    }
+
     /// Get a senses tags prettified
     #[inline]
     pub fn get_glosses(&self) -> String {
@@ -51,6 +88,12 @@ impl Sense {
         self.xref.as_ref().and_then(|xref| xref.split('・').next())
     }
 
+    /// Returns the parsed `xref` of the sense if available
+    #[inline]
+    pub fn get_xref_target(&self) -> Option<XRef<'_>> {
+        self.xref.as_deref().map(XRef::parse)
+    }
+
     /// Returns an `antonym` of the sense if available
     #[inline]
     pub fn get_antonym(&self) -> Option<&str> {
@@ -59,6 +102,60 @@ impl Sense {
             .and_then(|antonym| antonym.split('・').next())
     }
 
+    /// Merges `other` into `self` if both senses have the same language and glosses and their tags
+    /// don't contradict each other. Parts of speech get unioned. Returns `false` if the senses
+    /// can't be merged
+    pub fn try_merge(&mut self, other: &Sense) -> bool {
+        if self.language != other.language
+            || !self.has_same_glosses(other)
+            || !compatible(&self.misc, &other.misc)
+            || !compatible(&self.field, &other.field)
+            || !compatible(&self.dialect, &other.dialect)
+            || !compatible(&self.xref, &other.xref)
+            || !compatible(&self.antonym, &other.antonym)
+            || !compatible(&self.information, &other.information)
+            || !compatible(&self.example_sentence, &other.example_sentence)
+            || !compatible(&self.gairaigo, &other.gairaigo)
+            || self.restrictions != other.restrictions
+        {
+            return false;
+        }
+
+        for pos in other.part_of_speech.iter() {
+            if !self.part_of_speech.contains(pos) {
+                self.part_of_speech.push(*pos);
+            }
+        }
+
+        fill(&mut self.misc, &other.misc);
+        fill(&mut self.field, &other.field);
+        fill(&mut self.dialect, &other.dialect);
+        fill(&mut self.xref, &other.xref);
+        fill(&mut self.antonym, &other.antonym);
+        fill(&mut self.information, &other.information);
+        fill(&mut self.example_sentence, &other.example_sentence);
+        fill(&mut self.gairaigo, &other.gairaigo);
+
+        true
+    }
+
+    /// Returns `true` if the sense applies to the kanji form `kanji` or the reading `kana`
+    #[inline]
+    pub fn applies_to(&self, kanji: Option<&str>, kana: &str) -> bool {
+        self.restrictions.is_empty()
+            || self
+                .restrictions
+                .iter()
+                .any(|i| Some(i.as_str()) == kanji || i == kana)
+    }
+
+    /// Returns `true` if both senses have the same glosses, regardless of their order
+    fn has_same_glosses(&self, other: &Sense) -> bool {
+        let own = self.glosses.iter().map(|i| &i.gloss).sorted();
+        let other = other.glosses.iter().map(|i| &i.gloss).sorted();
+        own.eq(other)
+    }
+
     /// Get all pos_simple of a sense
     pub fn get_pos_simple(&self) -> Vec<PosSimple> {
         let mut pos_simple = self
@@ -143,3 +240,52 @@ impl Sense {
         }
     }
 }
+
+/// A reference to another word as used in `xref` and `ant` tags. Has the format
+/// `text[・reading][・sense number]`, eg. `語・ご・1`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct XRef<'a> {
+    /// Main reading (kanji or kana) of the referenced word
+    pub text: &'a str,
+    /// Kana reading to disambiguate words with the same `text`
+    pub reading: Option<&'a str>,
+    /// Number of the referenced sense, starting at 1
+    pub sense: Option<u8>,
+}
+
+impl<'a> XRef<'a> {
+    /// Parses an `xref` or `ant` value
+    pub fn parse(xref: &'a str) -> Self {
+        let mut parts = xref.split('・');
+        let text = parts.next().unwrap_or_default();
+
+        let mut reading = None;
+        let mut sense = None;
+        for part in parts {
+            match part.parse() {
+                Ok(num) => sense = Some(num),
+                Err(_) => reading = Some(part),
+            }
+        }
+
+        Self {
+            text,
+            reading,
+            sense,
+        }
+    }
+}
+
+/// Returns `true` if two optional tags don't contradict each other
+#[inline]
+fn compatible<T: PartialEq>(a: &Option<T>, b: &Option<T>) -> bool {
+    a.is_none() || b.is_none() || a == b
+}
+
+/// Sets `target` to `value` if `target` is `None`
+#[inline]
+fn fill<T: Clone>(target: &mut Option<T>, value: &Option<T>) {
+    if target.is_none() {
+        *target = value.clone();
+    }
+}
@@ -101,7 +101,7 @@ fn shorten_markdown(full: &str) -> &str {
     let line_count = full.split('\n').count().max(1);
     let conten_len = utils::real_string_len(full);
 
-    let mut end = full.len().min(50);
+    let mut end = utils::truncate_chars(full, 50).len();
 
     if conten_len > 100 {
         if line_count > 3 {
@@ -1,5 +1,6 @@
 use super::error::Error;
 use crate::models::accents::PitchItem;
+use itertools::Itertools;
 use std::{
     fs::File,
     io::{BufRead, BufReader, Seek, SeekFrom},
@@ -27,11 +28,15 @@ pub fn parse_item(line: String) -> Option<PitchItem> {
     let kana = split.next()?;
     let pitch = split.next()?;
 
+    // Accents are ordered by commonness, the most common one first
     let pitch = pitch
         .split(',')
         .into_iter()
         .map(|i| i.parse::<i32>().ok())
-        .collect::<Option<Vec<i32>>>()?;
+        .collect::<Option<Vec<i32>>>()?
+        .into_iter()
+        .unique()
+        .collect();
 
     Some(PitchItem {
         pitch,
@@ -39,3 +44,18 @@ pub fn parse_item(line: String) -> Option<PitchItem> {
         kana: kana.to_owned(),
     })
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_item_order() {
+        let item = parse_item("生\tなま\t1,0,1".to_string()).unwrap();
+        assert_eq!(item.kanji, "生");
+        assert_eq!(item.kana, "なま");
+        assert_eq!(item.pitch, vec![1, 0]);
+
+        assert!(parse_item("生\tなま\t1,a".to_string()).is_none());
+    }
+}
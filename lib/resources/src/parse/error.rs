@@ -9,11 +9,33 @@ pub enum Error {
     XmlError(quick_xml::Error),
     Utf8Error(FromUtf8Error),
     Utf8StrError(std::str::Utf8Error),
+    /// An UTF-8 error within the value of `element`, found at byte `position` of the input
+    Utf8Context {
+        element: String,
+        position: usize,
+        source: Box<Error>,
+    },
     ParseError,
     Undefined,
     IoError(std::io::Error),
 }
 
+impl Error {
+    /// Adds the `element` and byte `position` it occurred at to the error, if it is an UTF-8 error
+    pub fn with_utf8_context(self, element: &str, position: usize) -> Self {
+        match self {
+            Self::Utf8Error(_)
+            | Self::Utf8StrError(_)
+            | Self::XmlError(quick_xml::Error::Utf8(_)) => Self::Utf8Context {
+                element: element.to_string(),
+                position,
+                source: Box::new(self),
+            },
+            _ => self,
+        }
+    }
+}
+
 impl From<std::io::Error> for Error {
     fn from(err: std::io::Error) -> Self {
         Self::IoError(err)
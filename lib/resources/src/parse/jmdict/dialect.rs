@@ -1,36 +1,48 @@
 use std::fmt::Display;
 
 use localization::{language::Language, traits::Translatable, TranslationDict};
-use strum_macros::EnumString;
+use strum_macros::{AsRefStr, EnumString};
 
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, PartialEq, Clone, Copy, EnumString, Serialize, Deserialize, Hash)]
+#[derive(Debug, PartialEq, Clone, Copy, AsRefStr, EnumString, Serialize, Deserialize, Hash)]
 #[repr(u8)]
 pub enum Dialect {
     #[strum(serialize = "bra")]
+    #[serde(rename = "bra")]
     Brazilian,
     #[strum(serialize = "hob")]
+    #[serde(rename = "hob")]
     Hokkaido,
     #[strum(serialize = "ksb")]
+    #[serde(rename = "ksb")]
     Kansai,
     #[strum(serialize = "ktb")]
+    #[serde(rename = "ktb")]
     Kantou,
     #[strum(serialize = "kyb")]
+    #[serde(rename = "kyb")]
     Kyoto,
     #[strum(serialize = "kyu")]
+    #[serde(rename = "kyu")]
     Kyuushuu,
     #[strum(serialize = "nab")]
+    #[serde(rename = "nab")]
     Nagano,
     #[strum(serialize = "osb")]
+    #[serde(rename = "osb")]
     Osaka,
     #[strum(serialize = "rkb")]
+    #[serde(rename = "rkb")]
     Ryuukyuu,
     #[strum(serialize = "thb")]
+    #[serde(rename = "thb")]
     Touhoku,
     #[strum(serialize = "tsb")]
+    #[serde(rename = "tsb")]
     Tosa,
     #[strum(serialize = "tsug")]
+    #[serde(rename = "tsug")]
     Tsugaru,
 }
 
@@ -72,3 +84,51 @@ impl Translatable for Dialect {
         dict.gettext_fmt("{} dialect", &[self.gettext(dict, language)], language)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_serialize_code() {
+        assert_eq!(serde_json::to_string(&Dialect::Kansai).unwrap(), "\"ksb\"");
+        assert_eq!(Dialect::Kansai.as_ref(), "ksb");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let dialects = [
+            Dialect::Brazilian,
+            Dialect::Hokkaido,
+            Dialect::Kansai,
+            Dialect::Kantou,
+            Dialect::Kyoto,
+            Dialect::Kyuushuu,
+            Dialect::Nagano,
+            Dialect::Osaka,
+            Dialect::Ryuukyuu,
+            Dialect::Touhoku,
+            Dialect::Tosa,
+            Dialect::Tsugaru,
+        ];
+
+        for dialect in dialects {
+            assert_eq!(Dialect::from_str(dialect.as_ref()).unwrap(), dialect);
+
+            let json = serde_json::to_string(&dialect).unwrap();
+            assert_eq!(json, format!("\"{}\"", dialect.as_ref()));
+            assert_eq!(serde_json::from_str::<Dialect>(&json).unwrap(), dialect);
+
+            let stored = bincode::serialize(&dialect).unwrap();
+            assert_eq!(bincode::deserialize::<Dialect>(&stored).unwrap(), dialect);
+        }
+    }
+
+    #[test]
+    fn test_deserialize_code() {
+        let dialect: Dialect = serde_json::from_str("\"tsug\"").unwrap();
+        assert_eq!(dialect, Dialect::Tsugaru);
+        assert_eq!(Dialect::from_str("ksb").unwrap(), Dialect::Kansai);
+    }
+}
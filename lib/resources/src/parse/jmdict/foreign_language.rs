@@ -118,6 +118,18 @@ pub enum ForeignLanguage {
     Ainu,
     #[strum(serialize = "alg")]
     Algonquian,
+    #[strum(serialize = "fre")]
+    French,
+    #[strum(serialize = "spa")]
+    Spanish,
+    #[strum(serialize = "dut")]
+    Dutch,
+    #[strum(serialize = "rus")]
+    Russian,
+    #[strum(serialize = "swe")]
+    Swedish,
+    #[strum(serialize = "hun")]
+    Hungarian,
 }
 
 impl Translatable for ForeignLanguage {
@@ -179,6 +191,12 @@ impl Translatable for ForeignLanguage {
             ForeignLanguage::Finnish => "Finnish",
             ForeignLanguage::Ainu => "Ainu",
             ForeignLanguage::Algonquian => "Algonquian",
+            ForeignLanguage::French => "French",
+            ForeignLanguage::Spanish => "Spanish",
+            ForeignLanguage::Dutch => "Dutch",
+            ForeignLanguage::Russian => "Russian",
+            ForeignLanguage::Swedish => "Swedish",
+            ForeignLanguage::Hungarian => "Hungarian",
         }
     }
 }
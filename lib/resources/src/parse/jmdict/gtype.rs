@@ -5,14 +5,18 @@ use strum_macros::{AsRefStr, EnumString};
 use crate::parse::error;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, PartialEq, Clone, Copy, AsRefStr, EnumString, Serialize, Deserialize, Hash)]
+/// Type of a gloss, eg. whether it's a literal translation or an explanation
+#[derive(Debug, PartialEq, Eq, Clone, Copy, AsRefStr, EnumString, Serialize, Deserialize, Hash)]
 #[repr(u8)]
 pub enum GType {
     #[strum(serialize = "lit")]
+    #[serde(rename = "lit")]
     Literal,
     #[strum(serialize = "fig")]
+    #[serde(rename = "fig")]
     Figurative,
     #[strum(serialize = "expl")]
+    #[serde(rename = "expl")]
     Explanation,
 }
 
@@ -37,3 +41,16 @@ impl Into<i32> for GType {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_gtype_code() {
+        assert_eq!(GType::from_str("expl").unwrap(), GType::Explanation);
+        assert_eq!(GType::Figurative.as_ref(), "fig");
+        assert_eq!(serde_json::to_string(&GType::Literal).unwrap(), "\"lit\"");
+    }
+}
@@ -3,29 +3,54 @@ use strum_macros::{AsRefStr, EnumString};
 
 use serde::{Deserialize, Serialize};
 
+/// Information about a kanji or kana form of a word
 #[derive(Debug, PartialEq, Clone, Copy, AsRefStr, EnumString, Serialize, Deserialize, Hash, Eq)]
 #[repr(u8)]
 pub enum Information {
     #[strum(serialize = "ateji")]
+    #[serde(rename = "ateji")]
     Ateji,
     #[strum(serialize = "ik")]
+    #[serde(rename = "ik")]
     IrregularKana,
     #[strum(serialize = "iK")]
+    #[serde(rename = "iK")]
     IrregularKanji,
     #[strum(serialize = "io")]
+    #[serde(rename = "io")]
     IrregularOkurigana,
     #[strum(serialize = "oK")]
+    #[serde(rename = "oK")]
     OutdatedKanji,
     #[strum(serialize = "ok")]
+    #[serde(rename = "ok")]
     OutdatedKana,
     #[strum(serialize = "gikun")]
+    #[serde(rename = "gikun")]
     Gikun,
     #[strum(serialize = "uK")]
+    #[serde(rename = "uK")]
     UsuallyKana,
     #[strum(serialize = "rK")]
+    #[serde(rename = "rK")]
     RarelyUsedKanjiForm,
 }
 
+impl Information {
+    /// Returns `true` if the information marks an irregular or outdated form which shouldn't be
+    /// used when writing the word
+    #[inline]
+    pub fn is_irregular(&self) -> bool {
+        matches!(
+            self,
+            Self::IrregularKana
+                | Self::IrregularKanji
+                | Self::IrregularOkurigana
+                | Self::OutdatedKanji
+        )
+    }
+}
+
 impl Translatable for Information {
     fn get_id(&self) -> &'static str {
         match self {
@@ -41,3 +66,24 @@ impl Translatable for Information {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_irregular_round_trip() {
+        for code in ["ik", "iK", "io", "oK"] {
+            let info = Information::from_str(code).unwrap();
+            assert!(info.is_irregular());
+            assert_eq!(info.as_ref(), code);
+
+            let json = serde_json::to_string(&info).unwrap();
+            assert_eq!(json, format!("\"{}\"", code));
+            assert_eq!(serde_json::from_str::<Information>(&json).unwrap(), info);
+        }
+
+        assert!(!Information::Ateji.is_irregular());
+    }
+}
@@ -51,6 +51,8 @@ pub struct EntryElement {
     pub priorities: Vec<Priority>,
     pub reading_info: Vec<Information>,
     pub no_true_reading: bool,
+    /// Kanji forms a reading exclusively applies to. Applies to all kanji forms if empty
+    pub restrictions: Vec<String>,
 }
 
 /// A single 'sense' item for an entry
@@ -66,6 +68,9 @@ pub struct EntrySense {
     pub information: Option<String>,
     pub gairaigo: Option<Gairaigo>,
     pub example_sentence: Option<u32>,
+    /// Kanji forms (stagk) and readings (stagr) the sense exclusively applies to. Applies to all
+    /// forms if empty
+    pub restrictions: Vec<String>,
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize, Hash)]
@@ -77,8 +82,11 @@ pub struct Translation {
 #[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize, Hash)]
 pub struct Gairaigo {
     pub language: ForeignLanguage,
+    /// Whether the word is wasei (made in Japan), eg. サラリーマン
     pub fully_derived: bool,
     pub original: String,
+    /// Whether the word is only partially derived from `original`
+    pub partial: bool,
 }
 
 /// A single gloss entry.
@@ -209,10 +217,16 @@ where
          */
         let mut stack: Vec<Tag> = Vec::new();
 
+        // Name of the last opened element, used as context for errors
+        let mut element_name: Vec<u8> = Vec::new();
+
         loop {
             match self.reader.read_event(&mut self.buf)? {
                 // Some tag was opened
                 Event::Start(start) => {
+                    element_name.clear();
+                    element_name.extend_from_slice(start.name());
+
                     let tag =
                         Tag::from_str(str::from_utf8(start.name())?, Some(start.attributes()));
 
@@ -258,15 +272,23 @@ where
                 // Received some text
                 Event::Text(text) => {
                     if let Some(tag) = stack.last() {
-                        let value = text.unescape_and_decode_with_custom_entities(
-                            &self.reader,
-                            &custom_entities,
-                        )?;
+                        let position = self.reader.buffer_position();
+                        let value = text
+                            .unescape_and_decode_with_custom_entities(
+                                &self.reader,
+                                &custom_entities,
+                            )
+                            .map_err(|err| {
+                                Error::from(err).with_utf8_context(
+                                    &String::from_utf8_lossy(&element_name),
+                                    position,
+                                )
+                            })?;
 
                         match tag {
                             // Elements
                             Tag::Keb | Tag::Reb => {
-                                element.value = value;
+                                element.value = clean_reading(&value);
                                 element.kanji = *tag == Tag::Keb;
                             }
                             Tag::KePri | Tag::RePri => {
@@ -277,6 +299,7 @@ where
                             Tag::KeInf | Tag::ReInf => {
                                 element.reading_info.push(Information::from_str(&value)?)
                             }
+                            Tag::ReRestr => element.restrictions.push(clean_reading(&value)),
 
                             // Senses
                             Tag::Gloss(gloss) => {
@@ -299,6 +322,9 @@ where
                             Tag::Xref => sense.xref = Some(value),
                             Tag::Dialect => sense.dialect = Some(Dialect::from_str(&value)?),
                             Tag::SInf => sense.information = Some(value),
+                            Tag::Stagk | Tag::Stagr => {
+                                sense.restrictions.push(clean_reading(&value))
+                            }
                             Tag::ExampleSrcID(src) => {
                                 if src == "tat" {
                                     let id: u32 = value.parse()?;
@@ -335,6 +361,27 @@ where
 }
 
 impl Entry {
+    /// Returns all kana elements which are a valid reading of the kanji form `kanji`
+    pub fn readings_of<'a>(&'a self, kanji: &'a str) -> impl Iterator<Item = &'a EntryElement> {
+        self.elements
+            .iter()
+            .filter(move |i| !i.kanji && !i.no_true_reading && i.applies_to(kanji))
+    }
+
+    /// Returns all senses which apply to the kanji form `kanji` or the reading `kana`
+    pub fn senses_of<'a>(
+        &'a self,
+        kanji: Option<&'a str>,
+        kana: &'a str,
+    ) -> impl Iterator<Item = &'a EntrySense> {
+        self.senses.iter().filter(move |i| {
+            i.restrictions.is_empty()
+                || i.restrictions
+                    .iter()
+                    .any(|i| Some(i.as_str()) == kanji || i == kana)
+        })
+    }
+
     /// Apply a given Tag to the Entry
     fn apply_tag(&mut self, tag: &Tag, value: String) -> Result<(), Error> {
         #[allow(clippy::collapsible_match)]
@@ -347,12 +394,20 @@ impl Entry {
 }
 
 impl EntryElement {
+    /// Returns `true` if the element isn't restricted to kanji forms other than `kanji`
+    #[inline]
+    pub fn applies_to(&self, kanji: &str) -> bool {
+        self.restrictions.is_empty() || self.restrictions.iter().any(|i| i == kanji)
+    }
+
     #[inline]
     fn clear(&mut self) {
         self.kanji = false;
         self.value.clear();
         self.priorities.clear();
         self.reading_info.clear();
+        self.no_true_reading = false;
+        self.restrictions.clear();
     }
 }
 
@@ -381,6 +436,7 @@ impl EntrySense {
         self.part_of_speech.clear();
         self.example_sentence = None;
         self.gairaigo = None;
+        self.restrictions.clear();
     }
 }
 
@@ -492,6 +548,15 @@ impl Tag {
     }
 }
 
+/// Removes surrounding whitespace and a leading UTF-8 BOM from a kanji or kana reading
+fn clean_reading(value: &str) -> String {
+    value
+        .trim()
+        .trim_start_matches('\u{FEFF}')
+        .trim()
+        .to_string()
+}
+
 fn parse_ex_srce(attributes: Option<Attributes>) -> String {
     attributes
         .and_then(|attributes| {
@@ -539,6 +604,7 @@ fn parse_gairaigo(attributes: Option<Attributes>) -> Gairaigo {
                 gairaigo.language = ForeignLanguage::from_str(val).unwrap_or_default();
             }
             "ls_wasei" => gairaigo.fully_derived = val == "y",
+            "ls_type" => gairaigo.partial = val == "part",
             _ => continue,
         }
     }
@@ -551,3 +617,162 @@ impl Display for Tag {
         write!(f, "{:?}", self)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Parses all entries of a JMdict xml string
+    fn parse(xml: &str) -> Vec<Entry> {
+        let mut entries = vec![];
+        Parser::new(xml.as_bytes())
+            .parse(|entry, _| {
+                entries.push(entry);
+                false
+            })
+            .unwrap();
+        entries
+    }
+
+    #[test]
+    fn test_parse_gloss_type() {
+        let entries = parse(
+            r#"<JMdict><entry>
+                <ent_seq>1000000</ent_seq>
+                <r_ele><reb>さくら</reb></r_ele>
+                <sense>
+                    <gloss>cherry blossom</gloss>
+                    <gloss g_type="expl">flower of the cherry tree</gloss>
+                </sense>
+            </entry></JMdict>"#,
+        );
+
+        let glosses = &entries[0].senses[0].glosses;
+        assert_eq!(glosses[0].g_type, None);
+        assert_eq!(glosses[1].g_type, Some(GType::Explanation));
+        assert_eq!(glosses[1].value, "flower of the cherry tree");
+    }
+
+    #[test]
+    fn test_parse_reading_restriction() {
+        let entries = parse(
+            r#"<JMdict><entry>
+                <ent_seq>1000002</ent_seq>
+                <k_ele><keb>一寸</keb></k_ele>
+                <k_ele><keb>鳥渡</keb></k_ele>
+                <r_ele><reb>ちょっと</reb></r_ele>
+                <r_ele><reb>いっすん</reb><re_restr>一寸</re_restr></r_ele>
+                <sense><gloss>a little</gloss></sense>
+            </entry></JMdict>"#,
+        );
+        let entry = &entries[0];
+
+        let readings = |kanji| {
+            entry
+                .readings_of(kanji)
+                .map(|i| i.value.as_str())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(readings("一寸"), vec!["ちょっと", "いっすん"]);
+        assert_eq!(readings("鳥渡"), vec!["ちょっと"]);
+    }
+
+    #[test]
+    fn test_parse_sense_restriction() {
+        let entries = parse(
+            r#"<JMdict><entry>
+                <ent_seq>1000004</ent_seq>
+                <k_ele><keb>一寸</keb></k_ele>
+                <k_ele><keb>鳥渡</keb></k_ele>
+                <r_ele><reb>ちょっと</reb></r_ele>
+                <r_ele><reb>いっすん</reb><re_restr>一寸</re_restr></r_ele>
+                <sense><gloss>a little</gloss></sense>
+                <sense><stagk>一寸</stagk><stagr>いっすん</stagr><gloss>one sun</gloss></sense>
+            </entry></JMdict>"#,
+        );
+        let entry = &entries[0];
+        assert!(entry.senses[0].restrictions.is_empty());
+        assert_eq!(entry.senses[1].restrictions, vec!["一寸", "いっすん"]);
+
+        let glosses = |kanji, kana| {
+            entry
+                .senses_of(kanji, kana)
+                .map(|i| i.glosses[0].value.as_str())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            glosses(Some("一寸"), "ちょっと"),
+            vec!["a little", "one sun"]
+        );
+        assert_eq!(glosses(Some("鳥渡"), "ちょっと"), vec!["a little"]);
+        assert_eq!(glosses(None, "いっすん"), vec!["a little", "one sun"]);
+    }
+
+    #[test]
+    fn test_parse_reading_bom_whitespace() {
+        let entries = parse(
+            "<JMdict><entry>
+                <ent_seq>1000003</ent_seq>
+                <k_ele><keb>\u{FEFF}桜 </keb></k_ele>
+                <r_ele><reb>\u{FEFF}さくら </reb><re_restr> 桜</re_restr></r_ele>
+                <sense><gloss>cherry blossom</gloss></sense>
+            </entry></JMdict>",
+        );
+
+        let elements = &entries[0].elements;
+        assert_eq!(elements[0].value, "桜");
+        assert_eq!(elements[1].value, "さくら");
+        assert_eq!(elements[1].restrictions, vec!["桜".to_string()]);
+    }
+
+    #[test]
+    fn test_invalid_utf8_context() {
+        let mut xml = b"<JMdict><entry><ent_seq>1</ent_seq><sense><gloss>".to_vec();
+        xml.extend_from_slice(&[0x66, 0xFF, 0x6F]);
+        xml.extend_from_slice(b"</gloss></sense></entry></JMdict>");
+
+        let res = Parser::new(xml.as_slice()).parse(|_, _| false);
+        match res {
+            Err(Error::Utf8Context {
+                element, position, ..
+            }) => {
+                assert_eq!(element, "gloss");
+                assert!(position > 0);
+            }
+            _ => panic!("expected an UTF-8 error with context"),
+        }
+    }
+
+    #[test]
+    fn test_parse_loan_source() {
+        let entries = parse(
+            r#"<JMdict><entry>
+                <ent_seq>1000001</ent_seq>
+                <r_ele><reb>クロワッサン</reb></r_ele>
+                <sense>
+                    <lsource xml:lang="fre">croissant</lsource>
+                    <gloss>croissant</gloss>
+                </sense>
+                <sense>
+                    <lsource xml:lang="eng" ls_type="part" ls_wasei="y">cream</lsource>
+                    <gloss>cream puff</gloss>
+                </sense>
+            </entry></JMdict>"#,
+        );
+
+        let senses = &entries[0].senses;
+        assert_eq!(
+            senses[0].gairaigo,
+            Some(Gairaigo {
+                language: ForeignLanguage::French,
+                fully_derived: false,
+                original: "croissant".to_string(),
+                partial: false,
+            })
+        );
+
+        let partial = senses[1].gairaigo.as_ref().unwrap();
+        assert_eq!(partial.language, ForeignLanguage::English);
+        assert!(partial.partial && partial.fully_derived);
+    }
+}
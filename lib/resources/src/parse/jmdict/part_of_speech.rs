@@ -449,19 +449,21 @@ impl TryFrom<&str> for VerbType {
     type Error = error::Error;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        if value.len() < 2 || value[..1] != *"v" {
+        if value.len() < 2 || !value.starts_with('v') {
             return Err(error::Error::Undefined);
         }
 
-        Ok(match &value[1..2] {
+        let ending = value.get(2..).unwrap_or_default();
+
+        Ok(match value.get(1..2).unwrap_or_default() {
             "1" => match value {
                 "v1" => VerbType::Ichidan,
                 "v1-s" => VerbType::IchidanKureru,
                 _ => return Err(error::Error::Undefined),
             },
             "2" => VerbType::Nidan(NidanVerb::try_from(value)?), // Nidan
-            "4" => VerbType::Yodan(VerbEnding::try_from(&value[2..3])?), // Yodan
-            "5" => VerbType::Godan(GodanVerbEnding::try_from(&value[2..])?), // Godan
+            "4" => VerbType::Yodan(VerbEnding::try_from(ending.get(..1).unwrap_or_default())?), // Yodan
+            "5" => VerbType::Godan(GodanVerbEnding::try_from(ending)?), // Godan
             _ => match value {
                 "vi" => VerbType::Intransitive,
                 "vt" => VerbType::Transitive,
@@ -537,7 +539,7 @@ impl TryFrom<&str> for GodanVerbEnding {
             "uru" => GodanVerbEnding::Uru,
             "r-i" => GodanVerbEnding::RuIrreg,
             "k-s" => GodanVerbEnding::IkuYuku,
-            _ => match &value[0..1] {
+            _ => match value.get(0..1).unwrap_or_default() {
                 "b" => GodanVerbEnding::Bu,
                 "k" => GodanVerbEnding::Ku,
                 "g" => GodanVerbEnding::Gu,
@@ -615,7 +617,7 @@ impl TryFrom<&str> for NidanVerb {
     type Error = error::Error;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        if value.len() < 3 || value[..1] != *"v" {
+        if value.len() < 3 || !value.starts_with('v') {
             return Err(error::Error::Undefined);
         }
 
@@ -626,13 +628,13 @@ impl TryFrom<&str> for NidanVerb {
             });
         }
 
-        let class: VerbClass = match &value[4..5] {
+        let class: VerbClass = match value.get(4..5).unwrap_or_default() {
             "k" => VerbClass::Upper,
             "s" => VerbClass::Lower,
             _ => return Err(error::Error::Undefined),
         };
 
-        let ending = VerbEnding::try_from(&value[2..3])?;
+        let ending = VerbEnding::try_from(value.get(2..3).unwrap_or_default())?;
 
         Ok(NidanVerb { class, ending })
     }
@@ -657,7 +659,7 @@ impl TryFrom<&str> for NounType {
     type Error = error::Error;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        Ok(match &value[2..] {
+        Ok(match value.get(2..).unwrap_or_default() {
             "adv" => NounType::Adverbial,
             "pref" => NounType::Prefix,
             "suf" => NounType::Suffix,
@@ -690,7 +692,7 @@ impl TryFrom<&str> for AdjectiveType {
     type Error = error::Error;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        Ok(match value[4..].as_ref() {
+        Ok(match value.get(4..).unwrap_or_default() {
             "f" => AdjectiveType::PreNounVerb,
             "i" => AdjectiveType::Keiyoushi,
             "ix" => AdjectiveType::KeiyoushiYoiIi,
@@ -806,6 +808,14 @@ mod test {
         let s: String = pos.into();
         assert_eq!(start_str, s);
     }
+
+    #[test]
+    fn test_invalid_pos() {
+        for value in ["v4", "v2", "vあ", "あい", "adj", "n-"] {
+            let pos: Result<PartOfSpeech, Error> = value.try_into();
+            assert!(pos.is_err(), "{}", value);
+        }
+    }
 }
 /*
     #[test]
@@ -0,0 +1,99 @@
+use itertools::Itertools;
+use japanese::JapaneseExt;
+use resources::models::kanji::Kanji;
+use serde::Serialize;
+
+/// The readings and primary meaning of a kanji within a word
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct KanjiAnnotation {
+    pub literal: char,
+    pub onyomi: Vec<String>,
+    pub kunyomi: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meaning: Option<String>,
+}
+
+impl From<&Kanji> for KanjiAnnotation {
+    #[inline]
+    fn from(kanji: &Kanji) -> Self {
+        Self {
+            literal: kanji.literal,
+            onyomi: kanji.onyomi.clone().unwrap_or_default(),
+            kunyomi: kanji.kunyomi.clone().unwrap_or_default(),
+            meaning: kanji.meanings.first().cloned(),
+        }
+    }
+}
+
+/// Returns the readings and primary meaning of each known kanji within `word`, in the order they
+/// appear. Non kanji characters are skipped
+pub fn annotate_word(word: &str) -> Vec<KanjiAnnotation> {
+    let kanji_storage = resources::get().kanji();
+    annotate_with(word, |literal| kanji_storage.by_literal(literal))
+}
+
+/// Annotates the kanji of `word` found by `lookup`
+fn annotate_with<'a, F>(word: &str, lookup: F) -> Vec<KanjiAnnotation>
+where
+    F: Fn(char) -> Option<&'a Kanji>,
+{
+    word.chars()
+        .filter(|i| i.is_kanji() || i.is_rare_kanji())
+        .unique()
+        .filter_map(lookup)
+        .map(KanjiAnnotation::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn kanji(literal: char, onyomi: &[&str], kunyomi: &[&str], meanings: &[&str]) -> Kanji {
+        let to_vec = |i: &[&str]| Some(i.iter().map(|i| i.to_string()).collect());
+        Kanji {
+            literal,
+            onyomi: to_vec(onyomi),
+            kunyomi: to_vec(kunyomi),
+            meanings: meanings.iter().map(|i| i.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_annotate_word() {
+        let stored = vec![
+            kanji('学', &["ガク"], &["まな.ぶ"], &["study", "learning"]),
+            kanji('校', &["コウ", "キョウ"], &[], &["exam", "school"]),
+        ];
+        let lookup = |literal: char| stored.iter().find(|i| i.literal == literal);
+
+        let annotations = annotate_with("学校", lookup);
+        assert_eq!(annotations.len(), 2);
+
+        assert_eq!(annotations[0].literal, '学');
+        assert_eq!(annotations[0].onyomi, vec!["ガク"]);
+        assert_eq!(annotations[0].kunyomi, vec!["まな.ぶ"]);
+        assert_eq!(annotations[0].meaning.as_deref(), Some("study"));
+
+        assert_eq!(annotations[1].literal, '校');
+        assert_eq!(annotations[1].onyomi, vec!["コウ", "キョウ"]);
+        assert!(annotations[1].kunyomi.is_empty());
+    }
+
+    #[test]
+    fn test_annotate_skips_non_kanji() {
+        let stored = vec![kanji('学', &["ガク"], &["まな.ぶ"], &["study"])];
+        let lookup = |literal: char| stored.iter().find(|i| i.literal == literal);
+
+        let literals = |word: &str| {
+            annotate_with(word, lookup)
+                .into_iter()
+                .map(|i| i.literal)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(literals("学ぶ"), vec!['学']);
+        assert_eq!(literals("学校で学ぶ"), vec!['学']);
+        assert!(literals("まなぶ").is_empty());
+    }
+}
@@ -0,0 +1,98 @@
+use itertools::Itertools;
+use resources::{
+    models::{
+        kanji::Kanji,
+        words::{filter_languages, Word},
+    },
+    parse::jmdict::languages::Language,
+};
+
+/// Max amount of kanji returned by [`grid_info`]
+const MAX_KANJI: usize = 100;
+
+/// Max amount of words returned per kanji by [`grid_info`]
+const MAX_WORDS_PER_KANJI: usize = 10;
+
+/// Returns each known kanji of `literals` along with up to `words_per_kanji` of its compounds,
+/// for displaying several kanji at once. Kun compounds come first, unknown literals are skipped
+pub fn grid_info(
+    literals: &[char],
+    words_per_kanji: usize,
+    language: Language,
+    show_english: bool,
+) -> Vec<(Kanji, Vec<Word>)> {
+    let kanji_storage = resources::get().kanji();
+    let word_storage = resources::get().words();
+
+    literals
+        .iter()
+        .unique()
+        .filter_map(|literal| kanji_storage.by_literal(*literal))
+        .take(MAX_KANJI)
+        .map(|kanji| {
+            let mut words = top_compounds(kanji, words_per_kanji)
+                .filter_map(|seq| word_storage.by_sequence(seq))
+                .cloned()
+                .collect::<Vec<_>>();
+            filter_languages(words.iter_mut(), language, show_english);
+            (kanji.clone(), words)
+        })
+        .collect()
+}
+
+/// Returns the sequence ids of the first `limit` compounds of `kanji`, kun compounds first.
+/// `limit` is capped at [`MAX_WORDS_PER_KANJI`]
+fn top_compounds(kanji: &Kanji, limit: usize) -> impl Iterator<Item = u32> + '_ {
+    kanji
+        .kun_dicts
+        .iter()
+        .chain(kanji.on_dicts.iter())
+        .flatten()
+        .copied()
+        .unique()
+        .take(limit.min(MAX_WORDS_PER_KANJI))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn kanji(literal: char, kun_dicts: &[u32], on_dicts: &[u32]) -> Kanji {
+        Kanji {
+            literal,
+            kun_dicts: Some(kun_dicts.to_vec()),
+            on_dicts: Some(on_dicts.to_vec()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_top_compounds() {
+        let grid = vec![
+            kanji('食', &[1, 2], &[3, 4]),
+            kanji('飲', &[5], &[6, 7]),
+            kanji('水', &[], &[8, 9, 10]),
+        ];
+
+        let top = grid
+            .iter()
+            .map(|i| top_compounds(i, 3).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(top, vec![vec![1, 2, 3], vec![5, 6, 7], vec![8, 9, 10]]);
+    }
+
+    #[test]
+    fn test_top_compounds_limit() {
+        let seqs = (0..20).collect::<Vec<_>>();
+        let shared = kanji('食', &[1, 2], &[2, 3]);
+
+        assert_eq!(
+            top_compounds(&shared, 10).collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+        assert_eq!(
+            top_compounds(&kanji('食', &seqs, &[]), 100).count(),
+            MAX_WORDS_PER_KANJI
+        );
+    }
+}
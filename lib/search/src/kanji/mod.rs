@@ -1,9 +1,19 @@
+mod annotate;
+mod grid;
 mod order;
 pub mod result;
+mod similar;
 mod tag_only;
 
+pub use annotate::{annotate_word, KanjiAnnotation};
+pub use grid::grid_info;
+pub use similar::find_similar;
+
 use itertools::Itertools;
-use resources::models::kanji::Kanji;
+use resources::models::{
+    kanji::{Kanji, ReadingType},
+    words::Word,
+};
 use result::Item;
 
 use error::Error;
@@ -21,14 +31,18 @@ use crate::{
 use super::query::Query;
 
 // Defines the result of a kanji search
-#[derive(Default)]
+#[derive(Debug, Clone, PartialEq, Default)]
 pub struct KanjiResult {
     pub items: Vec<Item>,
     pub total_items: usize,
 }
 
-/// The entry of a kanji search
+/// The entry of a kanji search. Returns an empty `KanjiResult` if nothing was found
 pub fn search(query: &Query) -> Result<KanjiResult, Error> {
+    if query.only_symbols {
+        return Ok(KanjiResult::default());
+    }
+
     if query.form.is_tag_only() {
         return tag_only::search(query);
     }
@@ -93,7 +107,7 @@ fn all_kanji_from_text(text: &str) -> Vec<Kanji> {
 
     text.chars()
         .into_iter()
-        .filter(|i| i.is_kanji())
+        .filter(|i| i.is_kanji() || i.is_rare_kanji())
         .filter_map(|literal| kanji_storage.by_literal(literal))
         .cloned()
         .take(100)
@@ -108,7 +122,7 @@ pub fn guess_result(query: &Query) -> Option<Guess> {
     let guess = query_str
         .chars()
         .into_iter()
-        .filter(|i| i.is_kanji())
+        .filter(|i| i.is_kanji() || i.is_rare_kanji())
         .filter_map(|literal| kanji_storage.by_literal(literal))
         .take(15)
         .count();
@@ -116,6 +130,62 @@ pub fn guess_result(query: &Query) -> Option<Guess> {
     Some(Guess::new(guess as u32, GuessType::Accurate))
 }
 
+/// Returns all compounds of the kanji `literal` in which it is read with its `reading_type`
+/// reading, in the order they were stored
+pub fn compounds(literal: char, reading_type: ReadingType) -> Vec<Word> {
+    let kanji_storage = resources::get().kanji();
+    let word_storage = resources::get().words();
+    compounds_with(
+        literal,
+        reading_type,
+        |literal| kanji_storage.by_literal(literal),
+        |seq| word_storage.by_sequence(seq),
+    )
+}
+
+/// Returns the compounds of the kanji `literal` found by `kanji_lookup`, with their words found
+/// by `word_lookup`
+fn compounds_with<'a, K, W>(
+    literal: char,
+    reading_type: ReadingType,
+    kanji_lookup: K,
+    word_lookup: W,
+) -> Vec<Word>
+where
+    K: Fn(char) -> Option<&'a Kanji>,
+    W: Fn(u32) -> Option<&'a Word>,
+{
+    let kanji = match kanji_lookup(literal) {
+        Some(kanji) => kanji,
+        None => return vec![],
+    };
+
+    let dicts = match reading_type {
+        ReadingType::Kunyomi => &kanji.kun_dicts,
+        ReadingType::Onyomi => &kanji.on_dicts,
+    };
+
+    dicts
+        .as_ref()
+        .map(|dicts| {
+            dicts
+                .iter()
+                .filter_map(|seq| word_lookup(*seq))
+                .cloned()
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Returns the `ReadingType` the kanji `literal` has, if it is read as `segment_reading` within a
+/// word. Returns `None` if the kanji is unknown or the reading type is ambiguous
+pub fn classify_reading(literal: char, segment_reading: &str) -> Option<ReadingType> {
+    resources::get()
+        .kanji()
+        .by_literal(literal)?
+        .classify_reading(segment_reading)
+}
+
 /// Find kanji by mits meaning
 fn by_meaning(meaning: &str) -> Vec<Kanji> {
     let mut out = Vec::new();
@@ -144,3 +214,40 @@ fn to_item(items: Vec<Kanji>, query: &Query) -> Vec<Item> {
 fn format_query(query: &str) -> String {
     query.replace(" ", "").replace(".", "").trim().to_string()
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn word(sequence: u32) -> Word {
+        Word {
+            sequence,
+            ..Default::default()
+        }
+    }
+
+    fn compound_seqs(literal: char, reading_type: ReadingType) -> Vec<u32> {
+        let sei = Kanji {
+            literal: '生',
+            kun_dicts: Some(vec![3, 1]),
+            on_dicts: Some(vec![2, 42, 4]),
+            ..Default::default()
+        };
+        let words = vec![word(1), word(2), word(3), word(4)];
+
+        let kanji_lookup = |literal: char| Some(&sei).filter(|i| i.literal == literal);
+        let word_lookup = |seq: u32| words.iter().find(|i| i.sequence == seq);
+        compounds_with(literal, reading_type, kanji_lookup, word_lookup)
+            .into_iter()
+            .map(|i| i.sequence)
+            .collect()
+    }
+
+    #[test]
+    fn test_compounds_order() {
+        // Unknown sequences are skipped, the stored order is kept
+        assert_eq!(compound_seqs('生', ReadingType::Kunyomi), vec![3, 1]);
+        assert_eq!(compound_seqs('生', ReadingType::Onyomi), vec![2, 4]);
+        assert!(compound_seqs('木', ReadingType::Kunyomi).is_empty());
+    }
+}
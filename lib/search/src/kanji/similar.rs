@@ -0,0 +1,117 @@
+use std::cmp::Ordering;
+
+use resources::models::kanji::Kanji;
+
+/// Max amount of similar kanji returned by [`find_similar`]
+const MAX_SIMILAR: usize = 10;
+
+/// Returns kanji which share the most parts with the kanji `literal`. Kanji sharing more parts
+/// come first, ties are broken by the difference in stroke count. The kanji itself is excluded.
+pub fn find_similar(literal: char) -> Vec<Kanji> {
+    let kanji_storage = resources::get().kanji();
+    let lookup = |literal: char| kanji_storage.by_literal(literal);
+    similar_with(literal, lookup, kanji_storage.iter())
+}
+
+/// Returns the kanji of `candidates` similar to the kanji `literal` found by `lookup`
+fn similar_with<'a, F, I>(literal: char, lookup: F, candidates: I) -> Vec<Kanji>
+where
+    F: Fn(char) -> Option<&'a Kanji>,
+    I: Iterator<Item = &'a Kanji>,
+{
+    let kanji = match lookup(literal) {
+        Some(k) => k,
+        None => return vec![],
+    };
+
+    let parts = match kanji.parts.as_ref() {
+        Some(parts) => parts,
+        None => return vec![],
+    };
+
+    let mut similar = candidates
+        .filter(|i| i.literal != literal)
+        .filter_map(|i| {
+            let shared = shared_parts(parts, i.parts.as_ref()?);
+            (shared > 0).then(|| (i, shared))
+        })
+        .collect::<Vec<_>>();
+
+    similar.sort_by(|a, b| similarity_order(kanji.stroke_count, (a.1, a.0), (b.1, b.0)));
+
+    similar
+        .into_iter()
+        .take(MAX_SIMILAR)
+        .map(|i| i.0.clone())
+        .collect()
+}
+
+/// Returns the amount of parts `a` and `b` have in common
+fn shared_parts(a: &[char], b: &[char]) -> usize {
+    a.iter().filter(|i| b.contains(i)).count()
+}
+
+/// Orders two (shared parts, kanji) pairs by their similarity to a kanji with `stroke_count`
+/// strokes. More similar kanji are `Less`
+fn similarity_order(stroke_count: u8, a: (usize, &Kanji), b: (usize, &Kanji)) -> Ordering {
+    b.0.cmp(&a.0).then_with(|| {
+        let a_diff = utils::diff(a.1.stroke_count, stroke_count);
+        let b_diff = utils::diff(b.1.stroke_count, stroke_count);
+        a_diff.cmp(&b_diff)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn kanji(literal: char, stroke_count: u8, parts: &[char]) -> Kanji {
+        Kanji {
+            literal,
+            stroke_count,
+            parts: Some(parts.to_vec()),
+            ..Default::default()
+        }
+    }
+
+    fn similar(literal: char, stored: &[Kanji]) -> Vec<char> {
+        let lookup = |literal: char| stored.iter().find(|i| i.literal == literal);
+        similar_with(literal, lookup, stored.iter())
+            .into_iter()
+            .map(|i| i.literal)
+            .collect()
+    }
+
+    #[test]
+    fn test_similar_order() {
+        let stored = vec![
+            kanji('未', 5, &['一', '二', '木']),
+            kanji('休', 6, &['化', '木']),
+            kanji('末', 5, &['一', '木']),
+            kanji('二', 2, &['二']),
+            kanji('口', 3, &['口']),
+        ];
+
+        // 未 itself and 口 sharing no parts are excluded
+        assert_eq!(similar('未', &stored), vec!['末', '休', '二']);
+    }
+
+    #[test]
+    fn test_similar_limit() {
+        let mut stored = vec![kanji('木', 4, &['木'])];
+        let candidates = ('\u{4E00}'..).take(MAX_SIMILAR + 5);
+        stored.extend(candidates.map(|literal| kanji(literal, 5, &['木', '一'])));
+
+        assert_eq!(similar('木', &stored).len(), MAX_SIMILAR);
+    }
+
+    #[test]
+    fn test_similar_without_parts() {
+        let mut mi = kanji('未', 5, &[]);
+        mi.parts = None;
+        let stored = vec![mi, kanji('末', 5, &['一', '木'])];
+
+        assert!(similar('未', &stored).is_empty());
+        assert!(similar('本', &stored).is_empty());
+    }
+}
@@ -9,24 +9,39 @@ pub mod query_parser;
 pub mod radical;
 pub mod search_order;
 pub mod sentence;
+pub mod slow_query;
 pub mod suggestions;
 pub mod word;
 
-/// How string items should be matched with each other
-#[derive(Clone, Copy, PartialEq, Debug)]
+/// How string items should be matched with each other. The item is always the first and the
+/// query the second argument of [`SearchMode::str_eq`]. Prefix and suffix searches should use
+/// [`SearchMode::StartsWith`] and [`SearchMode::EndsWith`]. [`SearchMode::LeftVariable`] and
+/// [`SearchMode::RightVariable`] are aliases of them which are only kept for existing code
+#[derive(Clone, Copy, PartialEq, Debug, Hash)]
 #[repr(u8)]
 pub enum SearchMode {
+    /// The item equals the query
     Exact,
+    /// The item contains the query anywhere, eg. `cafe` matches `a cafe au lait`
     Variable,
+    /// Alias of [`SearchMode::EndsWith`]. New code should use `EndsWith`
     RightVariable,
+    /// Alias of [`SearchMode::StartsWith`]. New code should use `StartsWith`
     LeftVariable,
+    /// The item starts with the query, eg. `cafe` matches `cafe au lait`
+    StartsWith,
+    /// The item ends with the query, eg. `lait` matches `cafe au lait`
+    EndsWith,
 }
 
 impl SearchMode {
-    /// Compares a string based on the mode and case
+    /// Compares a string based on the mode and case. Ignoring the case ignores diacritics as well
     pub fn str_eq<S: AsRef<str>>(&self, a: S, b: S, ign_case: bool) -> bool {
         let (a, b) = if ign_case {
-            (a.as_ref().to_lowercase(), b.as_ref().to_lowercase())
+            (
+                utils::unaccent(&a.as_ref().to_lowercase()),
+                utils::unaccent(&b.as_ref().to_lowercase()),
+            )
         } else {
             (a.as_ref().to_owned(), b.as_ref().to_owned())
         };
@@ -34,18 +49,102 @@ impl SearchMode {
         match *self {
             SearchMode::Exact => a == b,
             SearchMode::Variable => a.contains(&b),
-            SearchMode::LeftVariable => a.starts_with(&b),
-            SearchMode::RightVariable => a.ends_with(&b),
+            SearchMode::StartsWith | SearchMode::LeftVariable => a.starts_with(&b),
+            SearchMode::EndsWith | SearchMode::RightVariable => a.ends_with(&b),
         }
     }
 
+    /// Returns the share of `a` covered by `b` if `b` matches `a` in this mode, or `None` if they
+    /// don't match
+    pub fn match_score<S: AsRef<str>>(&self, a: S, b: S, ign_case: bool) -> Option<f32> {
+        let a_len = a.as_ref().chars().count();
+        let b_len = b.as_ref().chars().count();
+
+        if !self.str_eq(a, b, ign_case) {
+            return None;
+        }
+
+        if a_len == 0 {
+            return Some(1f32);
+        }
+
+        Some((b_len as f32 / a_len as f32).min(1f32))
+    }
+
+    /// Returns the modes in the order matches are searched for when ranking words. Aliases are
+    /// left out
     pub fn ordered_iter() -> impl Iterator<Item = &'static SearchMode> {
         [
             SearchMode::Exact,
             SearchMode::Variable,
-            SearchMode::RightVariable,
-            SearchMode::LeftVariable,
+            SearchMode::EndsWith,
+            SearchMode::StartsWith,
         ]
         .iter()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_str_eq_ignore_case() {
+        assert!(SearchMode::Exact.str_eq("café", "cafe", true));
+        assert!(SearchMode::Exact.str_eq("Cafe", "cafe", true));
+        assert!(SearchMode::LeftVariable.str_eq("café au lait", "Cafe", true));
+        assert!(!SearchMode::Exact.str_eq("café", "cafe", false));
+    }
+
+    #[test]
+    fn test_str_eq_matrix() {
+        use SearchMode::*;
+
+        let item = "のみもの";
+        // (query, Exact, Variable, RightVariable, LeftVariable, StartsWith, EndsWith)
+        let matrix = [
+            ("のみもの", [true, true, true, true, true, true]),
+            ("のみ", [false, true, false, true, true, false]),
+            ("もの", [false, true, true, false, false, true]),
+            ("みも", [false, true, false, false, false, false]),
+            ("たべもの", [false, false, false, false, false, false]),
+            ("のみものや", [false, false, false, false, false, false]),
+            ("", [false, true, true, true, true, true]),
+        ];
+        let modes = [
+            Exact,
+            Variable,
+            RightVariable,
+            LeftVariable,
+            StartsWith,
+            EndsWith,
+        ];
+
+        for (query, expected) in matrix {
+            for (mode, expected) in modes.iter().zip(expected) {
+                let found = mode.str_eq(item, query, false);
+                assert_eq!(found, expected, "{:?} with {:?}", mode, query);
+            }
+        }
+    }
+
+    #[test]
+    fn test_starts_with() {
+        let mode = SearchMode::StartsWith;
+        assert!(mode.str_eq("たべもの", "たべ", false));
+        assert!(!mode.str_eq("たべもの", "もの", false));
+        assert_eq!(mode.match_score("たべもの", "たべ", false), Some(0.5));
+        assert_eq!(mode.match_score("たべもの", "たべもの", false), Some(1f32));
+        assert_eq!(mode.match_score("たべもの", "もの", false), None);
+    }
+
+    #[test]
+    fn test_ends_with() {
+        let mode = SearchMode::EndsWith;
+        assert!(mode.str_eq("のみもの", "もの", false));
+        assert!(!mode.str_eq("のみもの", "のみ", false));
+        assert_eq!(mode.match_score("のみもの", "の", false), Some(0.25));
+        assert_eq!(mode.match_score("Café", "FE", true), Some(0.5));
+        assert_eq!(mode.match_score("Café", "FE", false), None);
+    }
+}
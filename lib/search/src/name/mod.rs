@@ -8,6 +8,7 @@ use crate::{
         SearchEngine, SearchTask,
     },
     query::QueryLang,
+    slow_query,
 };
 
 use self::result::NameResult;
@@ -16,12 +17,23 @@ use super::query::Query;
 use error::Error;
 
 use japanese::JapaneseExt;
-use resources::models::names::Name;
+use resources::models::names::{Name, NAME_PART_SEPARATOR};
 use utils::to_option;
 
-/// Search for names
+/// Search for names. Returns an empty `NameResult` if nothing was found
 #[inline]
 pub fn search(query: &Query) -> Result<NameResult, Error> {
+    if query.only_symbols {
+        return Ok(NameResult::default());
+    }
+
+    let count =
+        |res: &Result<NameResult, Error>| res.as_ref().map(|i| i.total_count as usize).unwrap_or(0);
+
+    slow_query::measure("name", &query.query, || search_names(query), count)
+}
+
+fn search_names(query: &Query) -> Result<NameResult, Error> {
     if query.form.is_kanji_reading() {
         search_kanji(&query)
     } else {
@@ -34,17 +46,50 @@ pub fn search(query: &Query) -> Result<NameResult, Error> {
 }
 
 fn japanese_search(query: &Query) -> SearchTask<native::Engine> {
-    SearchTask::<native::Engine>::new(&query.query)
+    let mut task = SearchTask::<native::Engine>::new(&query.query)
         .threshold(0.05f32)
         .offset(query.page_offset)
-        .limit(query.settings.page_size as usize)
+        .limit(query.settings.page_size as usize);
+
+    // Foreign names separate their parts with a middle dot, eg. ジョン・スミス
+    if query.query.contains(NAME_PART_SEPARATOR) {
+        let parts = query.query.split(NAME_PART_SEPARATOR);
+        for part in parts.filter(|i| !i.is_empty()) {
+            task.add_query(part);
+        }
+    }
+
+    let query_str = query.query.clone();
+    task.set_order_fn(move |name, rel, _, _| name_part_order(name, rel, &query_str));
+
+    set_name_type_filter(&mut task, query);
+    task
+}
+
+/// Ranks names with a part matching the query, eg. ジョン・スミス for スミス, like exact matches
+fn name_part_order(name: &Name, relevance: f32, query: &str) -> usize {
+    let score = (relevance * 100f32) as usize;
+    if name.has_name_part(query) {
+        score.max(100)
+    } else {
+        score
+    }
 }
 
 fn foreign_search(query: &Query) -> SearchTask<foreign::Engine> {
-    SearchTask::<foreign::Engine>::new(&query.query)
+    let mut task = SearchTask::<foreign::Engine>::new(&query.query)
         .threshold(0.05f32)
         .offset(query.page_offset)
-        .limit(query.settings.page_size as usize)
+        .limit(query.settings.page_size as usize);
+    set_name_type_filter(&mut task, query);
+    task
+}
+
+/// Restricts the results of `task` to names of the queries name type, if set
+fn set_name_type_filter<T: SearchEngine<Output = Name>>(task: &mut SearchTask<T>, query: &Query) {
+    if let Some(name_type) = query.name_type {
+        task.set_result_filter(move |name| name.has_name_type(name_type));
+    }
 }
 
 fn handle_search<T: SearchEngine<Output = Name>>(task: SearchTask<T>) -> Result<NameResult, Error> {
@@ -62,8 +107,9 @@ fn search_kanji(query: &Query) -> Result<NameResult, Error> {
 
     let literal = kanji_reading.literal;
     let reading = kanji_reading.reading.clone();
+    let name_type = query.name_type;
     task.set_result_filter(move |name| {
-        if name.kanji.is_none() {
+        if name.kanji.is_none() || !name_type.map(|i| name.has_name_type(i)).unwrap_or(true) {
             return false;
         }
         let kanji = name.kanji.as_ref().unwrap();
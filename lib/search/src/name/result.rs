@@ -4,6 +4,7 @@ use resources::models::names::Name;
 
 use crate::engine::result::SearchResult;
 
+#[derive(Debug, Clone, PartialEq, Default)]
 pub struct NameResult {
     pub items: Vec<&'static Name>,
     pub total_count: u32,
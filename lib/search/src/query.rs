@@ -3,14 +3,23 @@ use std::{
     str::FromStr,
 };
 
-use crate::query_parser;
+use crate::{query_parser, search_order::NamedOrder, SearchMode};
 
 use super::query_parser::QueryType;
 
 use itertools::Itertools;
+use japanese::JapaneseExt;
 use resources::{
     models::kanji,
-    parse::jmdict::{languages::Language, misc::Misc, part_of_speech::PosSimple},
+    parse::{
+        jmdict::{
+            field::Field,
+            languages::Language,
+            misc::Misc,
+            part_of_speech::{PartOfSpeech, PosSimple},
+        },
+        jmnedict::name_type::NameType,
+    },
 };
 
 /// A single user provided query in a parsed format
@@ -29,8 +38,50 @@ pub struct Query {
     pub parse_japanese: bool,
     /// Whether to use the user query only or modify it if necessary
     pub use_original: bool,
+    /// Whether to only return common words in kanji reading searches
+    pub common_only: bool,
+    /// Whether to retry failed japanese word searches with deinflected forms of the query
+    pub auto_deinflect: bool,
+    /// Whether to search for japanese queries with expanded iteration marks as well
+    pub expand_iteration_marks: bool,
+    /// Max amount of kanji to load for word results. Defaults to [`MAX_KANJI_INFO_ITEMS`]
+    pub kanji_info_limit: Option<usize>,
+    /// Whether to not load any kanji for word results
+    pub skip_kanji_info: bool,
+    /// Only return names of this type in name searches
+    pub name_type: Option<NameType>,
+    /// Whether to put the word written as only the kanji to the top of kanji reading searches
+    pub include_kanji_word: bool,
+    /// Whether kanji reading searches list words written in kana with the same reading after
+    /// the compounds, eg. キ for 木 read as き
+    pub include_kana_words: bool,
+    /// Whether to find kana words with the same reading once normalized if nothing else was
+    /// found, eg. ラーメン for らあめん
+    pub normalize_kana: bool,
+    /// Whether to treat hiragana and katakana as distinct, eg. to not find コーヒー for こーひー.
+    /// Applies to all searches, even with `normalize_kana` set
+    pub strict_script: bool,
+    /// Set if the query marks the position of an affix with 〜, eg. 〜する or お〜
+    pub affix_mode: Option<SearchMode>,
+    /// Whether to find kana words spelled with or without a trailing ー, eg. コンピュータ and
+    /// コンピューター
+    pub ignore_trailing_long_mark: bool,
+    /// Whether kanji reading searches rank compounds using non jōyō kanji lower
+    pub prefer_joyo: bool,
+    /// Whether to search for well-known alternate spellings of the query too, eg. 子ども for 子供
+    pub expand_variants: bool,
+    /// Whether to merge senses of found words which have the same glosses into one sense
+    pub merge_senses: bool,
+    /// Ordering to sort the found words by instead of their relevance
+    pub order: Option<NamedOrder>,
+    /// Set if the query consists only of emojis or other symbols. Searches return no results for
+    /// such queries since they can't match anything
+    pub only_symbols: bool,
 }
 
+/// Default amount of kanji loaded alongside word results
+pub const MAX_KANJI_INFO_ITEMS: usize = 10;
+
 /// In-cookie saved personalized settings
 #[derive(Debug, Clone, Copy)]
 pub struct UserSettings {
@@ -42,12 +93,16 @@ pub struct UserSettings {
     pub page_size: u32,
     pub kanji_page_size: u32,
     pub show_example_sentences: bool,
+    /// Show japanese definitions in front of english ones if available
+    pub prefer_monolingual: bool,
 }
 
 impl PartialEq for UserSettings {
     #[inline]
     fn eq(&self, other: &Self) -> bool {
-        self.user_lang == other.user_lang && self.show_english == other.show_english
+        self.user_lang == other.user_lang
+            && self.show_english == other.show_english
+            && self.prefer_monolingual == other.prefer_monolingual
     }
 }
 
@@ -56,6 +111,7 @@ impl Hash for UserSettings {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.user_lang.hash(state);
         self.show_english.hash(state);
+        self.prefer_monolingual.hash(state);
     }
 }
 
@@ -71,6 +127,7 @@ impl Default for UserSettings {
             page_size: 10,
             kanji_page_size: 4,
             show_example_sentences: true,
+            prefer_monolingual: false,
         }
     }
 }
@@ -80,7 +137,9 @@ impl Default for UserSettings {
 pub enum Tag {
     SearchType(SearchTypeTag),
     PartOfSpeech(PosSimple),
+    DetailedPartOfSpeech(PartOfSpeech),
     Misc(Misc),
+    Field(Field),
     Jlpt(u8),
     GenkiLesson(u8),
 }
@@ -167,10 +226,21 @@ impl Tag {
         } else if let Some(tag) = Self::parse_search_type(s) {
             return Some(tag);
         } else {
-            match PosSimple::from_str(&s[1..]) {
-                Ok(pos) => return Some(Self::PartOfSpeech(pos)),
-                _ => return None,
+            if let Ok(pos) = PosSimple::from_str(&s[1..]) {
+                return Some(Self::PartOfSpeech(pos));
+            }
+
+            if let Ok(field) = Field::from_str(&s[1..]) {
+                return Some(Self::Field(field));
             }
+
+            if let Ok(misc) = Misc::from_str(&s[1..]) {
+                return Some(Self::Misc(misc));
+            }
+
+            PartOfSpeech::try_from(&s[1..])
+                .ok()
+                .map(Self::DetailedPartOfSpeech)
         }
     }
 
@@ -243,6 +313,15 @@ impl Tag {
         }
     }
 
+    #[inline]
+    pub fn as_detailed_part_of_speech(&self) -> Option<&PartOfSpeech> {
+        if let Self::DetailedPartOfSpeech(v) = self {
+            Some(v)
+        } else {
+            None
+        }
+    }
+
     /// Returns `true` if the tag is [`Misc`].
     ///
     /// [`Misc`]: Tag::Misc
@@ -260,6 +339,15 @@ impl Tag {
         }
     }
 
+    #[inline]
+    pub fn as_field(&self) -> Option<&Field> {
+        if let Self::Field(v) = self {
+            Some(v)
+        } else {
+            None
+        }
+    }
+
     /// Returns `true` if the tag is [`Jlpt`].
     ///
     /// [`Jlpt`]: Tag::Jlpt
@@ -299,6 +387,15 @@ impl Query {
         !self.query.is_empty()
     }
 
+    /// Returns a copy of the query searching for `query` instead while keeping all other settings
+    #[inline]
+    pub fn with_query(&self, query: String) -> Query {
+        Query {
+            query,
+            ..self.clone()
+        }
+    }
+
     /// Returns true if the query has at least one pos tag
     #[inline]
     pub fn has_part_of_speech_tags(&self) -> bool {
@@ -317,12 +414,37 @@ impl Query {
         self.tags.iter().filter_map(|i| i.as_part_of_speech())
     }
 
+    /// Returns an iterator over all detailed PartOfSpeech tags, eg. `#v1`
+    #[inline]
+    pub fn get_detailed_part_of_speech_tags(&self) -> impl Iterator<Item = &PartOfSpeech> + '_ {
+        self.tags
+            .iter()
+            .filter_map(|i| i.as_detailed_part_of_speech())
+    }
+
     /// Returns an iterator over all Misc tags
     #[inline]
     pub fn get_misc_tags(&self) -> impl Iterator<Item = &Misc> + '_ {
         self.tags.iter().filter_map(|i| i.as_misc())
     }
 
+    /// Returns an iterator over all Field tags
+    #[inline]
+    pub fn get_field_tags(&self) -> impl Iterator<Item = &Field> + '_ {
+        self.tags.iter().filter_map(|i| i.as_field())
+    }
+
+    /// Returns the max amount of kanji to load for word results. Returns 0 if kanji info loading
+    /// is skipped
+    #[inline]
+    pub fn get_kanji_info_limit(&self) -> usize {
+        if self.skip_kanji_info {
+            return 0;
+        }
+
+        self.kanji_info_limit.unwrap_or(MAX_KANJI_INFO_ITEMS)
+    }
+
     pub fn page_offset(&self, page_size: usize) -> usize {
         query_parser::calc_page_offset(self.page, page_size)
     }
@@ -347,6 +469,26 @@ impl Query {
             })
             .join(" ")
     }
+
+    /// Returns `true` if the query is likely a japanese sentence rather than a single word. This
+    /// is rather conservative to not tokenize single words which contain particle-like kana
+    pub fn looks_like_sentence(&self) -> bool {
+        let query = self.query.trim();
+        if !query.has_japanese() || query.chars().count() < 4 {
+            return false;
+        }
+
+        if query.ends_with('。') {
+            return true;
+        }
+
+        let parts = japanese::text_parts(query).collect::<Vec<_>>();
+        parts.len() >= 4
+            && parts
+                .iter()
+                .skip(1)
+                .any(|i| i.is_kana() && i.chars().next().unwrap().is_particle())
+    }
 }
 
 #[cfg(test)]
@@ -363,4 +505,77 @@ mod test {
         assert_eq!(Tag::parse_genki_tag("#genki3"), Some(Tag::GenkiLesson(3)));
         assert_eq!(Tag::parse_genki_tag("#genki23"), Some(Tag::GenkiLesson(23)));
     }
+
+    #[test]
+    fn test_parse_field_tag() {
+        assert_eq!(
+            Tag::parse_from_str("#med"),
+            Some(Tag::Field(Field::Medicine))
+        );
+        assert_eq!(Tag::parse_from_str("#nothing"), None);
+    }
+
+    #[test]
+    fn test_parse_misc_and_detailed_pos_tag() {
+        assert_eq!(
+            Tag::parse_from_str("#arch"),
+            Some(Tag::Misc(Misc::Archaism))
+        );
+        assert_eq!(
+            Tag::parse_from_str("#v1"),
+            Some(Tag::DetailedPartOfSpeech(PartOfSpeech::Verb(
+                VerbType::Ichidan
+            )))
+        );
+        assert_eq!(
+            Tag::parse_from_str("#v"),
+            Some(Tag::PartOfSpeech(PosSimple::Verb))
+        );
+    }
+
+    fn query(query: &str) -> Query {
+        Query {
+            query: query.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_looks_like_sentence() {
+        assert!(query("私は学生です").looks_like_sentence());
+        assert!(query("猫が好きです。").looks_like_sentence());
+        assert!(!query("食べる").looks_like_sentence());
+        assert!(!query("手の平").looks_like_sentence());
+        assert!(!query("取り扱い").looks_like_sentence());
+        assert!(!query("house").looks_like_sentence());
+    }
+
+    #[test]
+    fn test_with_query() {
+        let original = Query {
+            original_query: "食べてる".to_string(),
+            query: "食べてる".to_string(),
+            type_: QueryType::Words,
+            tags: vec![Tag::Jlpt(5)],
+            language: QueryLang::Japanese,
+            page_offset: 20,
+            page: 2,
+            word_index: 1,
+            common_only: true,
+            auto_deinflect: true,
+            kanji_info_limit: Some(3),
+            name_type: Some(NameType::Surname),
+            ..Default::default()
+        };
+
+        let derived = original.with_query("食べる".to_string());
+        assert_eq!(derived.query, "食べる");
+        assert_eq!(
+            derived,
+            Query {
+                query: "食べる".to_string(),
+                ..original.clone()
+            }
+        );
+    }
 }
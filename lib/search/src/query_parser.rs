@@ -8,6 +8,7 @@ use japanese::JapaneseExt;
 use resources::{models::kanji, parse::jmdict::part_of_speech::PosSimple};
 
 use super::query::{Form, Query, QueryLang, SearchTypeTag, Tag, UserSettings};
+use crate::SearchMode;
 
 /// Represents a query
 pub struct QueryParser {
@@ -20,6 +21,7 @@ pub struct QueryParser {
     page: usize,
     word_index: usize,
     use_original: bool,
+    affix_mode: Option<SearchMode>,
 }
 
 #[derive(Deserialize, Debug, Copy, Clone, PartialEq, Hash)]
@@ -111,6 +113,16 @@ impl QueryParser {
             //query.remove(query.len() - 1);
         }
 
+        // Dictionaries mark where affixes attach with a wave dash, eg. お〜 or 〜する
+        let (mut parsed_query, affix_mode) = parse_affix(parsed_query);
+
+        // IMEs can leave a part of the input unconverted, eg. tabeた
+        if !use_original {
+            if let Some(converted) = japanese::convert_mixed_romaji(&parsed_query) {
+                parsed_query = converted;
+            }
+        }
+
         QueryParser {
             q_type,
             query: parsed_query,
@@ -121,6 +133,7 @@ impl QueryParser {
             page,
             word_index,
             use_original,
+            affix_mode,
         }
     }
 
@@ -152,10 +165,14 @@ impl QueryParser {
             return None;
         }
 
+        // Queries consisting only of emojis or other symbols can't match anything
+        let only_symbols = !self.query.is_empty() && !has_searchable_text(&self.query);
+
         let parse_japanese = self.need_jp_parsing();
+        let language = parse_language(&self.query);
 
         Some(Query {
-            language: parse_language(&self.query),
+            language,
             type_: self.parse_query_type(),
             form: self.parse_form(),
             tags: self.tags,
@@ -167,6 +184,23 @@ impl QueryParser {
             word_index: self.word_index,
             parse_japanese,
             use_original: self.use_original,
+            common_only: false,
+            auto_deinflect: language == QueryLang::Japanese,
+            expand_iteration_marks: language == QueryLang::Japanese,
+            kanji_info_limit: None,
+            skip_kanji_info: false,
+            name_type: None,
+            include_kanji_word: false,
+            include_kana_words: false,
+            normalize_kana: false,
+            strict_script: false,
+            affix_mode: self.affix_mode,
+            ignore_trailing_long_mark: false,
+            prefer_joyo: false,
+            expand_variants: false,
+            merge_senses: false,
+            order: None,
+            only_symbols,
         })
     }
 
@@ -240,11 +274,18 @@ impl QueryParser {
 
     /// Returns Some(KanjiReading) if the query is a kanji reading query
     fn parse_kanji_reading(&self) -> Option<kanji::Reading> {
+        // Format of kanji query: '<Kanji>(<reading>)'
+        if let Some(reading) = parse_parenthesized_reading(&self.query) {
+            return Some(reading);
+        }
+
         // Format of kanji query: '<Kanji> <reading>'
         if utils::real_string_len(&self.query) >= 3 && self.query.contains(' ') {
             let split: Vec<_> = self.query.split(' ').collect();
 
-            if split[0].trim().is_kanji() && format_kanji_reading(split[1]).is_japanese() {
+            let literal = split[0].trim();
+            let is_kanji = literal.is_kanji() || literal.is_rare_kanji();
+            if is_kanji && format_kanji_reading(split[1]).is_japanese() {
                 // Kanji detected
                 return Some(kanji::Reading {
                     literal: split[0].chars().next().unwrap(),
@@ -257,6 +298,28 @@ impl QueryParser {
     }
 }
 
+/// Parses a kanji reading query in the format of '<Kanji>(<reading>)'. Full-width parentheses are
+/// supported as well
+fn parse_parenthesized_reading(query: &str) -> Option<kanji::Reading> {
+    let mut chars = query.trim().chars();
+
+    let literal = chars.next()?;
+    let is_kanji = literal.is_kanji() || literal.is_rare_kanji();
+    if !is_kanji || !matches!(chars.next()?, '(' | '（') {
+        return None;
+    }
+
+    let reading = chars.as_str().strip_suffix(&[')', '）'][..])?.trim();
+    if reading.is_empty() || !format_kanji_reading(reading).is_kana() {
+        return None;
+    }
+
+    Some(kanji::Reading {
+        literal,
+        reading: reading.to_string(),
+    })
+}
+
 /// Returns a number 0-100 of japanese character ratio
 fn get_jp_part(inp: &str) -> u8 {
     let mut total = 0;
@@ -285,6 +348,31 @@ pub fn parse_language(query: &str) -> QueryLang {
     }
 }
 
+/// Removes wave dashes (〜 or ～) marking the variable part of an affix from `query` and returns
+/// the mode the remaining text has to match words with, eg. `EndsWith` for 〜する
+fn parse_affix(query: String) -> (String, Option<SearchMode>) {
+    let is_dash = |c: char| c == '〜' || c == '～';
+    let text = query.trim_matches(is_dash);
+    if text.is_empty() || text.len() == query.len() || text.contains(is_dash) {
+        return (query, None);
+    }
+
+    let mode = match (query.starts_with(is_dash), query.ends_with(is_dash)) {
+        (true, true) => SearchMode::Variable,
+        (true, false) => SearchMode::EndsWith,
+        _ => SearchMode::StartsWith,
+    };
+
+    (text.to_string(), Some(mode))
+}
+
+/// Returns `true` if `query` contains at least one letter, digit or japanese character
+fn has_searchable_text(query: &str) -> bool {
+    query
+        .chars()
+        .any(|c| c.is_alphanumeric() || c.is_japanese())
+}
+
 #[inline]
 pub fn format_kanji_reading(s: &str) -> String {
     s.replace('.', "").replace('-', "").replace(' ', "")
@@ -293,3 +381,105 @@ pub fn format_kanji_reading(s: &str) -> String {
 pub fn calc_page_offset(page: usize, page_size: usize) -> usize {
     page.saturating_sub(1) * page_size
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse(query: &str) -> Option<Query> {
+        QueryParser::new(
+            query.to_string(),
+            QueryType::Words,
+            UserSettings::default(),
+            0,
+            0,
+            true,
+        )
+        .parse()
+    }
+
+    fn parse_form(query: &str) -> Form {
+        parse(query).unwrap().form
+    }
+
+    #[test]
+    fn test_parenthesized_kanji_reading() {
+        let expected = Form::KanjiReading(kanji::Reading {
+            literal: '生',
+            reading: "なま".to_string(),
+        });
+
+        assert_eq!(parse_form("生(なま)"), expected);
+        assert_eq!(parse_form("生（なま）"), expected);
+    }
+
+    #[test]
+    fn test_parenthesized_no_kanji_reading() {
+        assert!(parse_form("生(なま").as_kanji_reading().is_none());
+        assert!(parse_form("生()").as_kanji_reading().is_none());
+        assert!(parse_form("なま(生)").as_kanji_reading().is_none());
+    }
+
+    #[test]
+    fn test_inline_tags() {
+        let query = parse("食べる #v1").unwrap();
+        assert_eq!(query.query, "食べる");
+        assert_eq!(
+            query.get_detailed_part_of_speech_tags().collect::<Vec<_>>(),
+            vec![&PartOfSpeech::Verb(VerbType::Ichidan)]
+        );
+
+        let query = parse("water #kanji #unknowntag").unwrap();
+        assert_eq!(query.query, "water");
+        assert_eq!(query.type_, QueryType::Kanji);
+        assert_eq!(query.tags, vec![Tag::SearchType(SearchTypeTag::Kanji)]);
+    }
+
+    #[test]
+    fn test_symbol_only_query() {
+        let only_symbols = |query: &str| parse(query).unwrap().only_symbols;
+
+        assert!(only_symbols("😀"));
+        assert!(only_symbols("🍣🍺 !"));
+        assert!(only_symbols("🍣 #n5"));
+
+        assert!(!only_symbols("sushi 🍣"));
+        assert!(!only_symbols("寿司🍣"));
+        assert!(!only_symbols("100"));
+    }
+
+    #[test]
+    fn test_affix_query() {
+        let suffix = parse("〜する").unwrap();
+        assert_eq!(suffix.query, "する");
+        assert_eq!(suffix.original_query, "〜する");
+        assert_eq!(suffix.affix_mode, Some(SearchMode::EndsWith));
+
+        let prefix = parse("お〜").unwrap();
+        assert_eq!(prefix.query, "お");
+        assert_eq!(prefix.affix_mode, Some(SearchMode::StartsWith));
+
+        assert_eq!(
+            parse("～する").unwrap().affix_mode,
+            Some(SearchMode::EndsWith)
+        );
+        assert_eq!(
+            parse("〜的〜").unwrap().affix_mode,
+            Some(SearchMode::Variable)
+        );
+
+        assert_eq!(parse("する").unwrap().affix_mode, None);
+        assert_eq!(parse("〜").unwrap().affix_mode, None);
+        assert_eq!(parse("お〜する").unwrap().affix_mode, None);
+    }
+
+    #[test]
+    fn test_mixed_romaji_query() {
+        let query = parse("tabeた").unwrap();
+        assert_eq!(query.query, "たべた");
+        assert_eq!(query.original_query, "tabeた");
+        assert_eq!(query.language, QueryLang::Japanese);
+
+        assert_eq!(parse("\"tabeた\"").unwrap().query, "tabeた");
+    }
+}
@@ -1,18 +1,26 @@
-use super::query::Query;
+use std::str::FromStr;
 
+use super::{query::Query, word::order};
+
+use error::Error;
 use japanese::jp_parsing::WordItem;
+use resources::{models::words::Word, parse::jmdict::priority::Priority};
 
 pub struct SearchOrder<'a, 'parser> {
     pub query: &'a Query,
+    /// Data of the natural language parser for the query. `None` if the query wasn't parsed
     pub morpheme: &'a Option<WordItem<'parser, 'a>>,
 }
 
 impl<'a, 'parser> SearchOrder<'a, 'parser> {
+    /// Creates a new `SearchOrder`. Pass `None` as `morpheme` if the query hasn't been parsed
     #[inline]
     pub fn new(query: &'a Query, morpheme: &'a Option<WordItem<'parser, 'a>>) -> Self {
         SearchOrder { query, morpheme }
     }
 
+    /// Sorts `vec` by the scores of `order_fn`, highest first. The sort is stable, so items with
+    /// equal scores keep their previous order
     #[inline]
     pub fn sort<U, T>(&self, vec: &mut Vec<U>, order_fn: T)
     where
@@ -20,4 +28,186 @@ impl<'a, 'parser> SearchOrder<'a, 'parser> {
     {
         vec.sort_by(|a, b| order_fn(a, &self).cmp(&order_fn(b, &self)).reverse())
     }
+
+    /// Sorts `words` by the ordering `named`
+    #[inline]
+    pub fn sort_words(&self, words: &mut Vec<Word>, named: NamedOrder) {
+        self.sort(words, |word, search_order| named.score(word, search_order))
+    }
+
+    /// Returns the query string words should be ordered against
+    fn query_str(&self) -> &str {
+        self.morpheme
+            .as_ref()
+            .map(|i| i.get_lexeme())
+            .unwrap_or(self.query.query.as_str())
+    }
+}
+
+/// Word orderings which can be selected by their name
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NamedOrder {
+    /// How well the words reading matches the query
+    ReadingRelevance,
+    /// Most frequently used words first
+    Frequency,
+    /// Shortest readings first
+    Length,
+    /// Words of easier JLPT levels first
+    JlptEasyFirst,
+}
+
+impl NamedOrder {
+    /// Returns the score of `word` for the ordering. Words with higher scores are ordered first
+    pub fn score(&self, word: &Word, search_order: &SearchOrder) -> usize {
+        match self {
+            NamedOrder::ReadingRelevance => order::japanese_search_order(
+                word,
+                0f32,
+                search_order.query_str(),
+                Some(&search_order.query.original_query),
+            ),
+            NamedOrder::Frequency => frequency_score(word),
+            NamedOrder::Length => usize::MAX - word.get_reading().reading.chars().count(),
+            NamedOrder::JlptEasyFirst => word.jlpt_lvl.unwrap_or_default() as usize,
+        }
+    }
+}
+
+impl FromStr for NamedOrder {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.trim().to_lowercase().as_str() {
+            "reading_relevance" => Self::ReadingRelevance,
+            "frequency" => Self::Frequency,
+            "length" => Self::Length,
+            "jlpt_easy_first" => Self::JlptEasyFirst,
+            _ => return Err(Error::ParseError),
+        })
+    }
+}
+
+/// Scores words by their frequency rank (nf01-nf48). Common words without a rank are scored above
+/// uncommon ones
+pub(crate) fn frequency_score(word: &Word) -> usize {
+    let priorities = match word.get_reading().priorities.as_ref() {
+        Some(p) => p,
+        None => return 0,
+    };
+
+    priorities
+        .iter()
+        .filter_map(|i| match i {
+            Priority::Nf(rank) => Some(100 - *rank as usize),
+            _ => None,
+        })
+        .max()
+        .unwrap_or(1)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use resources::models::words::{Dict, Reading};
+
+    fn by_lexeme_len(item: &&str, order: &SearchOrder) -> usize {
+        match order.morpheme {
+            Some(morpheme) => (item.len() == morpheme.get_lexeme().len()) as usize,
+            None => item.len(),
+        }
+    }
+
+    #[test]
+    fn test_sort_without_morpheme() {
+        let query = Query::default();
+        let morpheme = None;
+        let order = SearchOrder::new(&query, &morpheme);
+
+        let mut items = vec!["a", "abc", "ab"];
+        order.sort(&mut items, by_lexeme_len);
+        assert_eq!(items, vec!["abc", "ab", "a"]);
+    }
+
+    #[test]
+    fn test_sort_with_morpheme() {
+        let query = Query::default();
+        let morpheme = Some(WordItem {
+            surface: "ab",
+            lexeme: "ab",
+            word_class: None,
+            was_in_db: true,
+            inflections: vec![],
+            start: 0,
+            original_word: "ab".to_string(),
+        });
+        let order = SearchOrder::new(&query, &morpheme);
+
+        let mut items = vec!["a", "abc", "ab"];
+        order.sort(&mut items, by_lexeme_len);
+        assert_eq!(items[0], "ab");
+    }
+
+    #[test]
+    fn test_sort_stable() {
+        let query = Query::default();
+        let morpheme = None;
+        let order = SearchOrder::new(&query, &morpheme);
+
+        let mut items = vec!["b", "dd", "a", "cc", "e", "fff"];
+        order.sort(&mut items, by_lexeme_len);
+        assert_eq!(items, vec!["fff", "dd", "cc", "b", "a", "e"]);
+    }
+
+    fn word(
+        sequence: u32,
+        kana: &str,
+        priorities: Option<Vec<Priority>>,
+        jlpt: Option<u8>,
+    ) -> Word {
+        Word {
+            sequence,
+            reading: Reading {
+                kana: Dict {
+                    reading: kana.to_string(),
+                    priorities,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            jlpt_lvl: jlpt,
+            ..Default::default()
+        }
+    }
+
+    fn sorted_by(name: &str) -> Vec<u32> {
+        let query = Query {
+            query: "たべる".to_string(),
+            original_query: "たべる".to_string(),
+            ..Default::default()
+        };
+        let morpheme = None;
+        let order = SearchOrder::new(&query, &morpheme);
+
+        let mut words = vec![
+            word(1, "たべる", None, None),
+            word(2, "たべるもの", Some(vec![Priority::Nf(1)]), Some(4)),
+            word(3, "た", Some(vec![Priority::Nf(20)]), Some(5)),
+        ];
+        order.sort_words(&mut words, NamedOrder::from_str(name).unwrap());
+        words.into_iter().map(|i| i.sequence).collect()
+    }
+
+    #[test]
+    fn test_named_orders() {
+        assert_eq!(sorted_by("reading_relevance"), vec![1, 2, 3]);
+        assert_eq!(sorted_by("frequency"), vec![2, 3, 1]);
+        assert_eq!(sorted_by("length"), vec![3, 1, 2]);
+        assert_eq!(sorted_by("jlpt_easy_first"), vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn test_named_order_invalid() {
+        assert!(NamedOrder::from_str("random").is_err());
+    }
 }
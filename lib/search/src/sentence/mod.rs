@@ -5,7 +5,7 @@ use std::time::Instant;
 
 use self::result::{Item, SentenceResult};
 
-use super::query::Query;
+use super::query::{Query, UserSettings};
 use crate::{
     engine::{guess::Guess, sentences::foreign, sentences::native, SearchEngine, SearchTask},
     query::{Form, QueryLang},
@@ -13,8 +13,12 @@ use crate::{
 use error::Error;
 use resources::{models::sentences::Sentence, parse::jmdict::languages::Language};
 
-/// Searches for sentences
+/// Searches for sentences. Returns an empty `SentenceResult` if nothing was found
 pub fn search(query: &Query) -> Result<SentenceResult, Error> {
+    if query.only_symbols {
+        return Ok(SentenceResult::default());
+    }
+
     let start = Instant::now();
 
     let res = match query.form {
@@ -119,6 +123,18 @@ fn map_sentence_to_item(sentence: &Sentence, lang: Language, query: &Query) -> O
     Some(result::Item { sentence })
 }
 
+/// Estimates the amount of example sentences containing `word` which have a translation
+/// matching `settings`
+pub fn example_count(word: &str, settings: UserSettings) -> Option<Guess> {
+    let query = Query {
+        query: word.to_string(),
+        settings,
+        ..Default::default()
+    };
+
+    jp_search(&query).estimate_result_count().ok()
+}
+
 /// Guesses the amount of results a search would return with given `query`
 pub fn guess_result(query: &Query) -> Option<Guess> {
     if query.language == QueryLang::Japanese {
@@ -5,13 +5,12 @@ use crate::query::{Query, Tag};
 
 use super::result::SentenceResult;
 
+/// Runs a tag only sentence search. Returns an empty result if no tag applies
 pub(super) fn search(query: &Query) -> Result<SentenceResult, Error> {
-    let filter_tag = query
-        .tags
-        .iter()
-        .find(|i| i.is_empty_allowed())
-        // We expect to find one since this function should only be called if there is one
-        .ok_or(Error::Unexpected)?;
+    let filter_tag = match query.tags.iter().find(|i| i.is_empty_allowed()) {
+        Some(filter_tag) => filter_tag,
+        None => return Ok(SentenceResult::default()),
+    };
 
     if let Tag::Jlpt(jlpt) = filter_tag {
         return jlpt_search(query, *jlpt);
@@ -52,3 +51,15 @@ fn jlpt_search(query: &Query, jlpt: u8) -> Result<SentenceResult, Error> {
         len,
     })
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_no_tag_empty_result() {
+        let res = search(&Query::default()).unwrap();
+        assert_eq!(res.len, 0);
+        assert!(res.items.is_empty());
+    }
+}
@@ -0,0 +1,92 @@
+use std::time::{Duration, Instant};
+
+use once_cell::sync::OnceCell;
+
+/// Searches taking longer than this get reported, unless configured otherwise
+pub const DEFAULT_THRESHOLD: Duration = Duration::from_secs(1);
+
+static THRESHOLD: OnceCell<Duration> = OnceCell::new();
+
+/// Sets the duration after which searches get reported as slow. Can only be set once
+pub fn set_threshold(threshold: Duration) {
+    THRESHOLD.set(threshold).ok();
+}
+
+/// Returns the duration after which searches get reported as slow
+#[inline]
+fn threshold() -> Duration {
+    THRESHOLD.get().copied().unwrap_or(DEFAULT_THRESHOLD)
+}
+
+/// Runs the search `f` and logs a warning containing `query` and the amount of results `count`
+/// returns for its output if the search took longer than the configured threshold
+pub(crate) fn measure<T, F, C>(kind: &str, query: &str, f: F, count: C) -> T
+where
+    F: FnOnce() -> T,
+    C: FnOnce(&T) -> usize,
+{
+    let (res, report) = measure_with(threshold(), kind, query, f, count);
+    if let Some(report) = report {
+        log::warn!("{}", report);
+    }
+    res
+}
+
+/// Runs the search `f` and returns its output along with a report message if it took longer than
+/// `threshold`
+fn measure_with<T, F, C>(
+    threshold: Duration,
+    kind: &str,
+    query: &str,
+    f: F,
+    count: C,
+) -> (T, Option<String>)
+where
+    F: FnOnce() -> T,
+    C: FnOnce(&T) -> usize,
+{
+    let start = Instant::now();
+    let res = f();
+    let elapsed = start.elapsed();
+
+    if elapsed <= threshold {
+        return (res, None);
+    }
+
+    let report = format!(
+        "Slow {} search for {:?} took {:?} ({} results)",
+        kind,
+        query,
+        elapsed,
+        count(&res)
+    );
+    (res, Some(report))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn test_report_slow_search() {
+        let slow = || {
+            thread::sleep(Duration::from_millis(20));
+            vec![1, 2, 3]
+        };
+
+        let threshold = Duration::from_millis(5);
+        let (res, report) = measure_with(threshold, "word", "食べる", slow, Vec::len);
+        assert_eq!(res, vec![1, 2, 3]);
+
+        let report = report.unwrap();
+        assert!(report.contains("食べる"));
+        assert!(report.contains("3 results"));
+    }
+
+    #[test]
+    fn test_fast_search_not_reported() {
+        let (_, report) = measure_with(Duration::from_secs(10), "word", "a", || 1, |_| 1);
+        assert!(report.is_none());
+    }
+}
@@ -0,0 +1,119 @@
+use std::cmp::Ordering;
+
+use itertools::Itertools;
+use japanese::JapaneseExt;
+use resources::models::{suggestions::native_words::NativeSuggestion, words::Word};
+use utils::binary_search::BinarySearchable;
+
+/// Max amount of suggestion items looked at before ordering
+const MAX_CANDIDATES: usize = 500;
+
+/// Returns up to `limit` words whose reading starts with `prefix`, most frequent ones first.
+/// Romaji prefixes are converted to hiragana before looking them up
+pub fn complete(prefix: &str, limit: usize) -> Vec<&'static Word> {
+    let prefix = format_prefix(prefix);
+    if prefix.is_empty() {
+        return vec![];
+    }
+
+    let dict = match resources::get().suggestions().japanese_words() {
+        Some(dict) => dict,
+        None => return vec![],
+    };
+    let word_storage = resources::get().words();
+
+    let mut candidates = dict
+        .search(|e: &NativeSuggestion| prefix_cmp(&e.text, &prefix))
+        .take(MAX_CANDIDATES)
+        .filter_map(|i| Some((word_storage.by_sequence(i.sequence)?, i.frequency)))
+        .unique_by(|i| i.0.sequence)
+        .collect::<Vec<_>>();
+
+    candidates.sort_by(|a, b| completion_order(*a, *b));
+
+    candidates.into_iter().take(limit).map(|i| i.0).collect()
+}
+
+/// Converts a (partially typed) romaji prefix into kana. Trailing letters which can't be converted
+/// yet, like the 'b' in 'tab', are dropped
+fn format_prefix(prefix: &str) -> String {
+    let prefix = prefix.trim();
+    if prefix.is_japanese() {
+        return prefix.to_string();
+    }
+
+    prefix
+        .to_hiragana()
+        .chars()
+        .take_while(|i| i.is_kana())
+        .collect()
+}
+
+/// Compare function matching all items starting with `prefix`
+#[inline]
+fn prefix_cmp(text: &str, prefix: &str) -> Ordering {
+    if text.starts_with(prefix) {
+        Ordering::Equal
+    } else {
+        text.cmp(prefix)
+    }
+}
+
+/// Orders (word, frequency) pairs. More frequent and common words come first, shorter words break
+/// ties
+fn completion_order(a: (&Word, u32), b: (&Word, u32)) -> Ordering {
+    b.1.cmp(&a.1)
+        .then_with(|| b.0.is_common().cmp(&a.0.is_common()))
+        .then_with(|| {
+            let a_len = a.0.get_reading().reading.chars().count();
+            let b_len = b.0.get_reading().reading.chars().count();
+            a_len.cmp(&b_len)
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use resources::{
+        models::words::{Dict, Reading},
+        parse::jmdict::priority::Priority,
+    };
+
+    fn word(kana: &str, kanji: &str, common: bool) -> Word {
+        Word {
+            reading: Reading {
+                kana: Dict {
+                    reading: kana.to_string(),
+                    ..Default::default()
+                },
+                kanji: Some(Dict {
+                    reading: kanji.to_string(),
+                    priorities: common.then(|| vec![Priority::Ichi(1)]),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_format_prefix() {
+        assert_eq!(format_prefix("たべ"), "たべ");
+        assert_eq!(format_prefix("tabe"), "たべ");
+        assert_eq!(format_prefix("tab"), "た");
+    }
+
+    #[test]
+    fn test_completion_order() {
+        let taberu = word("たべる", "食べる", true);
+        let tabemono = word("たべもの", "食べ物", true);
+        let tabekata = word("たべかた", "食べ方", false);
+
+        let mut items = vec![(&tabekata, 0), (&tabemono, 0), (&taberu, 0)];
+        items.sort_by(|a, b| completion_order(*a, *b));
+
+        assert_eq!(items[0].0.get_reading().reading, "食べる");
+        assert_eq!(items[2].0.get_reading().reading, "食べ方");
+    }
+}
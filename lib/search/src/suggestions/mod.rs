@@ -1,8 +1,11 @@
 mod binary_search;
+mod complete;
 mod jaro_search;
 pub mod store_item;
 pub mod text_store;
 
+pub use self::complete::complete;
+
 use self::{jaro_search::AsyncSearch, store_item::Item};
 use binary_search::Search as BinarySearch;
 use jaro_search::Search as JaroSearch;
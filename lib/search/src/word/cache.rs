@@ -0,0 +1,215 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use once_cell::sync::{Lazy, OnceCell};
+use resources::parse::jmdict::languages::Language;
+
+use super::ResultData;
+use crate::query::Query;
+
+/// Amount of kanji reading search results kept in the cache, unless configured otherwise
+pub const DEFAULT_CAPACITY: usize = 500;
+
+static CAPACITY: OnceCell<usize> = OnceCell::new();
+
+static CACHE: Lazy<Mutex<ReadingCache>> = Lazy::new(|| Mutex::new(ReadingCache::new(capacity())));
+
+/// Sets the amount of kanji reading search results to cache. `0` disables the cache. Can only be
+/// set once, before the first search
+pub fn set_capacity(capacity: usize) {
+    CAPACITY.set(capacity).ok();
+}
+
+/// Returns the amount of kanji reading search results to cache
+#[inline]
+fn capacity() -> usize {
+    CAPACITY.get().copied().unwrap_or(DEFAULT_CAPACITY)
+}
+
+/// Returns the cached result of the kanji reading search `query` or runs `f` and caches its
+/// output. Queries which can't be cached safely are always passed to `f`. Only results of the
+/// actual kanji reading search may be cached: searches falling back to a word search depend on
+/// query options which aren't part of the [`CacheKey`]
+pub(super) fn get_or_search<F, E>(query: &Query, f: F) -> Result<ResultData, E>
+where
+    F: FnOnce() -> Result<ResultData, E>,
+{
+    let key = match CacheKey::new(query) {
+        Some(key) => key,
+        None => return f(),
+    };
+
+    if let Some(res) = CACHE.lock().unwrap().get(&key) {
+        return Ok(res);
+    }
+
+    let res = f()?;
+    CACHE.lock().unwrap().insert(key, res.clone());
+    Ok(res)
+}
+
+/// Everything the result of a kanji reading search depends on
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct CacheKey {
+    literal: char,
+    reading: String,
+    language: Language,
+    show_english: bool,
+    prefer_monolingual: bool,
+    page_size: u32,
+    page_offset: usize,
+    common_only: bool,
+    include_kanji_word: bool,
+    include_kana_words: bool,
+    strict_script: bool,
+    prefer_joyo: bool,
+}
+
+impl CacheKey {
+    /// Creates a new `CacheKey` for a kanji reading search. Returns `None` for other searches
+    /// and for queries with tags, since those filter the results
+    fn new(query: &Query) -> Option<Self> {
+        let kanji_reading = query.form.as_kanji_reading()?;
+        if !query.tags.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            literal: kanji_reading.literal,
+            reading: kanji_reading.reading.clone(),
+            language: query.settings.user_lang,
+            show_english: query.settings.show_english,
+            prefer_monolingual: query.settings.prefer_monolingual,
+            page_size: query.settings.page_size,
+            page_offset: query.page_offset,
+            common_only: query.common_only,
+            include_kanji_word: query.include_kanji_word,
+            include_kana_words: query.include_kana_words,
+            strict_script: query.strict_script,
+            prefer_joyo: query.prefer_joyo,
+        })
+    }
+}
+
+/// A least recently used cache of kanji reading search results
+struct ReadingCache {
+    capacity: usize,
+    entries: HashMap<CacheKey, (ResultData, u64)>,
+    last_use: u64,
+}
+
+impl ReadingCache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: HashMap::with_capacity(capacity),
+            last_use: 0,
+        }
+    }
+
+    /// Returns a copy of the cached result for `key` and marks it as used
+    fn get(&mut self, key: &CacheKey) -> Option<ResultData> {
+        self.last_use += 1;
+        let (res, last_use) = self.entries.get_mut(key)?;
+        *last_use = self.last_use;
+        Some(res.clone())
+    }
+
+    /// Caches `res` for `key`, removing the least recently used result if the cache is full
+    fn insert(&mut self, key: CacheKey, res: ResultData) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        if self.entries.len() >= self.capacity && !self.entries.contains_key(&key) {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, last_use))| *last_use)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                self.entries.remove(&oldest);
+            }
+        }
+
+        self.last_use += 1;
+        self.entries.insert(key, (res, self.last_use));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::query::{Form, Tag};
+    use resources::{models::kanji::Reading, parse::jmdict::part_of_speech::PosSimple};
+
+    fn query(literal: char, reading: &str) -> Query {
+        Query {
+            form: Form::KanjiReading(Reading {
+                literal,
+                reading: reading.to_string(),
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn result(count: usize) -> ResultData {
+        ResultData {
+            count,
+            ..Default::default()
+        }
+    }
+
+    fn key(literal: char, reading: &str) -> CacheKey {
+        CacheKey::new(&query(literal, reading)).unwrap()
+    }
+
+    #[test]
+    fn test_repeat_query_cached() {
+        let mut cache = ReadingCache::new(10);
+        let nama = key('生', "なま");
+        assert_eq!(cache.get(&nama), None);
+
+        cache.insert(nama.clone(), result(3));
+        assert_eq!(cache.get(&nama), Some(result(3)));
+        assert_eq!(cache.get(&key('生', "せい")), None);
+    }
+
+    #[test]
+    fn test_evict_least_recently_used() {
+        let mut cache = ReadingCache::new(2);
+        cache.insert(key('生', "なま"), result(1));
+        cache.insert(key('生', "せい"), result(2));
+
+        // Use the first entry so the second one gets evicted
+        cache.get(&key('生', "なま"));
+        cache.insert(key('食', "た.べる"), result(3));
+
+        assert_eq!(cache.get(&key('生', "なま")), Some(result(1)));
+        assert_eq!(cache.get(&key('生', "せい")), None);
+        assert_eq!(cache.get(&key('食', "た.べる")), Some(result(3)));
+    }
+
+    #[test]
+    fn test_disabled_cache() {
+        let mut cache = ReadingCache::new(0);
+        cache.insert(key('生', "なま"), result(1));
+        assert_eq!(cache.get(&key('生', "なま")), None);
+    }
+
+    #[test]
+    fn test_cache_key() {
+        let mut german = query('生', "なま");
+        german.settings.user_lang = Language::German;
+        assert_ne!(CacheKey::new(&german), Some(key('生', "なま")));
+
+        let mut next_page = query('生', "なま");
+        next_page.page_offset = 10;
+        assert_ne!(CacheKey::new(&next_page), Some(key('生', "なま")));
+
+        let mut tagged = query('生', "なま");
+        tagged.tags.push(Tag::PartOfSpeech(PosSimple::Noun));
+        assert_eq!(CacheKey::new(&tagged), None);
+
+        assert_eq!(CacheKey::new(&Query::default()), None);
+    }
+}
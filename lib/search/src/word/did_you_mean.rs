@@ -0,0 +1,93 @@
+use std::cmp::Reverse;
+
+use japanese::JapaneseExt;
+use levenshtein::levenshtein;
+use resources::models::suggestions::native_words::NativeSuggestion;
+use utils::binary_search::BinarySearchable;
+
+/// Max amount of readings compared against the query
+const MAX_CANDIDATES: usize = 1000;
+
+/// Max edit distance between the query and a suggested reading
+const MAX_EDIT_DISTANCE: usize = 2;
+
+/// Returns the known reading closest to the kana `query` by edit distance, preferring more frequent
+/// words. Only readings starting with the same character as the query are considered to keep the
+/// candidate set small
+pub(super) fn suggest(query: &str) -> Option<String> {
+    let query = query.trim();
+    if query.is_empty() || !query.is_kana() {
+        return None;
+    }
+
+    let dict = resources::get().suggestions().japanese_words()?;
+    let first = query.chars().next()?.to_string();
+
+    let candidates = dict
+        .search(|e: &NativeSuggestion| prefix_cmp(&e.text, &first))
+        .take(MAX_CANDIDATES)
+        .collect::<Vec<_>>();
+
+    let candidates = candidates.iter().map(|i| (i.text.as_str(), i.frequency));
+    nearest(query, candidates).map(|i| i.to_string())
+}
+
+/// Returns the reading out of `candidates`, given as (reading, frequency) pairs, with the lowest
+/// edit distance to `query`. Ties are broken by the higher frequency. Readings which are too
+/// different from the query or equal to it are ignored
+fn nearest<'a>(query: &str, candidates: impl Iterator<Item = (&'a str, u32)>) -> Option<&'a str> {
+    let max_dist = (query.chars().count() / 3).clamp(1, MAX_EDIT_DISTANCE);
+
+    candidates
+        .filter(|i| i.0 != query)
+        .map(|(reading, frequency)| (reading, levenshtein(reading, query), frequency))
+        .filter(|i| i.1 <= max_dist)
+        .min_by_key(|i| (i.1, Reverse(i.2)))
+        .map(|i| i.0)
+}
+
+#[inline]
+fn prefix_cmp(text: &str, prefix: &str) -> std::cmp::Ordering {
+    if text.starts_with(prefix) {
+        std::cmp::Ordering::Equal
+    } else {
+        text.cmp(prefix)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_nearest() {
+        let candidates = [
+            ("たべもの", 10),
+            ("たべる", 50),
+            ("たべろ", 1),
+            ("たいへん", 20),
+        ];
+        assert_eq!(nearest("たべり", candidates.into_iter()), Some("たべる"));
+        assert_eq!(nearest("たべろ", candidates.into_iter()), Some("たべる"));
+        assert_eq!(nearest("ねこ", candidates.into_iter()), None);
+    }
+
+    #[test]
+    fn test_nearest_frequency() {
+        let candidates = [("たべろ", 1), ("たべる", 50)];
+        assert_eq!(nearest("たべれ", candidates.into_iter()), Some("たべる"));
+    }
+
+    #[test]
+    fn test_nearest_max_distance() {
+        let candidates = [("いただきます", 10), ("いただきました", 5)];
+        assert_eq!(
+            nearest("いたらきまそ", candidates.into_iter()),
+            Some("いただきます")
+        );
+        assert_eq!(
+            nearest("いたらきまそ", [("いたいです", 10)].into_iter()),
+            None
+        );
+    }
+}
@@ -12,7 +12,8 @@ use resources::models::{
     words::Word,
 };
 
-/// Runs a kanji reading search
+/// Runs a kanji reading search. An unknown kanji results in an empty `ResultData` rather than an
+/// error
 pub(super) fn by_reading(search: &Search<'_>) -> Result<ResultData, Error> {
     let reading = search
         .query
@@ -22,18 +23,72 @@ pub(super) fn by_reading(search: &Search<'_>) -> Result<ResultData, Error> {
 
     let kanji_storage = resources::get().kanji();
 
-    let kanji = kanji_storage
-        .by_literal(reading.literal)
-        .ok_or(Error::Undefined)?;
+    let kanji = match kanji_storage.by_literal(reading.literal) {
+        Some(kanji) => kanji,
+        None => return Ok(ResultData::default()),
+    };
+
+    // Full kun readings can be provided without the okurigana separator, eg. 食 with たべる
+    let kanji_reading = match kanji.find_reading(&reading.reading) {
+        Some(kanji_reading) => kanji_reading,
+        None => return alternative_reading_search(search),
+    };
+
+    let reading_type = match kanji.get_reading_type(kanji_reading) {
+        Some(reading_type) => reading_type,
+        None => return alternative_reading_search(search),
+    };
+
+    super::cache::get_or_search(search.query, || {
+        reading_search(kanji, reading_type, kanji_reading, search.query)
+    })
+}
+
+/// Searches for words using `kanji` read as `kanji_reading`
+fn reading_search(
+    kanji: &Kanji,
+    reading_type: ReadingType,
+    kanji_reading: &str,
+    query: &Query,
+) -> Result<ResultData, Error> {
+    let (mut words, mut count) =
+        words_with_kanji_reading(kanji, reading_type, kanji_reading, query)?;
 
-    let reading_type = kanji.get_reading_type(&reading.reading);
-    if !kanji.has_reading(&reading.reading) || reading_type.is_none() {
-        return alternative_reading_search(search);
+    // The kanji itself can be a word read with the searched reading, eg. 生 read as なま
+    if query.include_kanji_word && query.page_offset == 0 {
+        if let Some(word) = kanji_word(kanji, reading_type, kanji_reading, query) {
+            let len = words.len();
+            words.retain(|i| i.sequence != word.sequence);
+            if words.len() == len {
+                count += 1;
+            }
+            words.insert(0, word);
+        }
     }
-    let reading_type = reading_type.unwrap();
 
-    let (words, count) =
-        words_with_kanji_reading(kanji, reading_type, &reading.reading, search.query)?;
+    // Words usually written in kana are read the same way but can't be found by the literal.
+    // They're listed after all compounds
+    if query.include_kana_words {
+        let kana_words = kana_words_with_reading(kanji_reading, query);
+        let page = page_after(
+            &kana_words,
+            count,
+            words.len(),
+            query.page_offset,
+            query.settings.page_size as usize,
+        );
+
+        let mut page = page.iter().map(|i| (*i).clone()).collect::<Vec<_>>();
+        super::filter_languages_with(
+            page.iter_mut(),
+            query.settings.user_lang,
+            query.settings.show_english,
+            query.settings.prefer_monolingual,
+        );
+
+        count += kana_words.len();
+        words.extend(page);
+    }
 
     Ok(ResultData {
         count,
@@ -58,8 +113,9 @@ fn words_with_kanji_reading(
     let literal = kanji.literal.to_string();
     let reading = reading.to_string();
     let literal_reading = kanji.get_literal_reading(&reading);
+    let common_only = query.common_only;
     search_task.set_result_filter(move |word| {
-        if word.reading.kanji.is_none() {
+        if word.reading.kanji.is_none() || !common_filter(word, common_only) {
             return false;
         }
 
@@ -88,9 +144,11 @@ fn words_with_kanji_reading(
 
             let e = readings.unwrap().iter().any(|i| {
                 i.0.contains(&literal)
-                    && i.1
-                        .to_hiragana()
-                        .contains(&literal_reading.as_ref().unwrap().to_hiragana())
+                    && reading_in_segment(
+                        &i.1.to_hiragana(),
+                        &literal_reading.as_ref().unwrap().to_hiragana(),
+                        MAX_READING_LEN_RATIO,
+                    )
                     && kana
                         .to_hiragana()
                         .contains(&kanji::format_reading(&reading.to_hiragana()))
@@ -105,42 +163,207 @@ fn words_with_kanji_reading(
     });
 
     let kanji_reading = query.form.as_kanji_reading().unwrap().clone();
+    let prefer_joyo = query.prefer_joyo;
     search_task.set_order_fn(move |word, rel, _, _| {
-        order::kanji_reading_search(word, &kanji_reading, rel)
+        let score = order::kanji_reading_search(word, &kanji_reading, rel);
+        if prefer_joyo {
+            joyo_order(score, word, is_joyo)
+        } else {
+            score
+        }
     });
 
     let res = search_task.find()?;
-    let len = res.len();
-    let mut words = res.item_iter().cloned().collect::<Vec<_>>();
+    let total = res.len();
+    let found = res.item_iter().collect::<Vec<_>>();
 
-    super::filter_languages(
+    // Words can be found through multiple of their readings
+    let mut words: Vec<Word> = Vec::with_capacity(found.len());
+    for word in found.iter() {
+        if !words.iter().any(|i| i.same_lemma(word)) {
+            words.push((*word).clone());
+        }
+    }
+    let len = total.saturating_sub(found.len() - words.len());
+
+    super::filter_languages_with(
         words.iter_mut(),
         query.settings.user_lang,
         query.settings.show_english,
+        query.settings.prefer_monolingual,
     );
 
     Ok((words, len))
 }
 
+/// Returns the word written as the kanji with the given `reading` (including its okurigana) and
+/// read as `reading`
+fn kanji_word(
+    kanji: &Kanji,
+    reading_type: ReadingType,
+    reading: &str,
+    query: &Query,
+) -> Option<Word> {
+    let written = kanji::format_reading_with_literal(kanji.literal, reading, reading_type);
+    // Words written with kanji are read in hiragana, even for on readings
+    let kana = kanji::format_reading(reading).to_hiragana();
+
+    let mut word = resources::get()
+        .words()
+        .by_normalized_reading(&kana)
+        .find(|word| {
+            is_kanji_word(word, &written, &kana, query.strict_script)
+                && common_filter(word, query.common_only)
+        })?
+        .clone();
+
+    super::filter_languages_with(
+        std::iter::once(&mut word),
+        query.settings.user_lang,
+        query.settings.show_english,
+        query.settings.prefer_monolingual,
+    );
+
+    Some(word)
+}
+
+/// Returns `true` if `word` is written as `written` and read as `kana`
+#[inline]
+fn is_kanji_word(word: &Word, written: &str, kana: &str, strict_script: bool) -> bool {
+    let kanji = word.reading.kanji.as_ref().map(|i| i.reading.as_str());
+    kanji == Some(written) && has_kana_reading(word, kana, strict_script)
+}
+
+/// Returns all words written in kana which are read as the kanji `reading`
+fn kana_words_with_reading(reading: &str, query: &Query) -> Vec<&'static Word> {
+    let reading = kanji::format_reading(reading);
+    let pos_filter = utils::to_option(query.get_part_of_speech_tags().copied().collect());
+
+    resources::get()
+        .words()
+        .by_normalized_reading(&reading)
+        .filter(|word| {
+            word.reading.kanji.is_none()
+                && has_kana_reading(word, &reading, query.strict_script)
+                && common_filter(word, query.common_only)
+                && Search::word_filter(query, word, &pos_filter)
+        })
+        .collect()
+}
+
+/// Returns the items of `extra` belonging to the page at `offset` with `limit` items, if `extra`
+/// is listed after `total` other items of which `shown` are on the page
+pub(super) fn page_after<T>(
+    extra: &[T],
+    total: usize,
+    shown: usize,
+    offset: usize,
+    limit: usize,
+) -> &[T] {
+    let start = offset.saturating_sub(total).min(extra.len());
+    let end = (start + limit.saturating_sub(shown)).min(extra.len());
+    &extra[start..end]
+}
+
+/// Returns `true` if the kana form of `word` equals `reading`, regardless of the words kanji.
+/// With `strict_script` set, the kana form has to be written in the same kana script as `reading`
+#[inline]
+fn has_kana_reading(word: &Word, reading: &str, strict_script: bool) -> bool {
+    let kana = &word.reading.kana.reading;
+    if strict_script && super::kana_script(kana) != super::kana_script(reading) {
+        return false;
+    }
+
+    japanese::normalize_reading(kana) == japanese::normalize_reading(reading)
+}
+
+/// Max ratio between the length of a kana segment and a kanji reading found in its middle
+const MAX_READING_LEN_RATIO: usize = 3;
+
+/// Returns `true` if `segment` contains `reading`. Readings more than `max_ratio` times shorter
+/// than `segment` only match at its start or end, since they'd match unrelated compounds otherwise
+fn reading_in_segment(segment: &str, reading: &str, max_ratio: usize) -> bool {
+    if segment.starts_with(reading) || segment.ends_with(reading) {
+        return true;
+    }
+
+    segment.contains(reading) && segment.chars().count() <= reading.chars().count() * max_ratio
+}
+
+/// Score subtracted from compounds using non jōyō kanji
+const NON_JOYO_PENALTY: usize = 30;
+
+/// Demotes the `score` of `word` if its kanji reading uses kanji which aren't jōyō kanji
+fn joyo_order<F: Fn(char) -> bool>(score: usize, word: &Word, is_joyo: F) -> usize {
+    let kanji = match word.reading.kanji.as_ref() {
+        Some(kanji) => &kanji.reading,
+        None => return score,
+    };
+
+    let only_joyo = kanji
+        .chars()
+        .filter(|i| i.is_kanji() || i.is_rare_kanji())
+        .all(is_joyo);
+
+    if only_joyo {
+        score
+    } else {
+        score.saturating_sub(NON_JOYO_PENALTY)
+    }
+}
+
+/// Returns `true` if `literal` is a jōyō kanji. Unknown kanji aren't jōyō kanji
+#[inline]
+fn is_joyo(literal: char) -> bool {
+    resources::get()
+        .kanji()
+        .by_literal(literal)
+        .map(|i| i.is_joyo())
+        .unwrap_or(false)
+}
+
+/// Returns `false` if `common_only` is set and `word` is not a common word
+#[inline]
+fn common_filter(word: &Word, common_only: bool) -> bool {
+    !common_only || word.is_common()
+}
+
 /// Do a search without the kanji literal or reading
 fn alternative_reading_search(search: &Search<'_>) -> Result<ResultData, Error> {
     let reading = search.query.form.as_kanji_reading().unwrap();
 
     // Modify search query
-    Search {
-        query: &Query {
-            query: kanji::literal_kun_reading(&reading.reading),
-            ..search.query.to_owned()
-        },
+    let mut res = Search {
+        query: &search
+            .query
+            .with_query(kanji::literal_kun_reading(&reading.reading)),
     }
-    .do_word_search()
+    .do_word_search()?;
+
+    res.used_fallback = true;
+    Ok(res)
 }
 
-/// Load word assigned kanji
-pub(super) fn load_word_kanji_info(words: &[Word]) -> Result<Vec<Kanji>, Error> {
+/// Load word assigned kanji. Returns at most `limit` kanji
+pub(super) fn load_word_kanji_info(words: &[Word], limit: usize) -> Result<Vec<Kanji>, Error> {
+    if limit == 0 {
+        return Ok(vec![]);
+    }
+
     let kanji_resources = resources::get().kanji();
 
-    let kanji_literals = words
+    let kanji_literals = word_kanji_literals(words)
+        .filter_map(|i| kanji_resources.by_literal(i).cloned())
+        .take(limit)
+        .collect::<Vec<_>>();
+
+    Ok(kanji_literals)
+}
+
+/// Returns all kanji used in the kanji readings of `words` in order of occurrence, without
+/// duplicates
+fn word_kanji_literals(words: &[Word]) -> impl Iterator<Item = char> + '_ {
+    words
         .iter()
         .filter_map(|i| {
             let kanji = &i.reading.kanji.as_ref()?.reading;
@@ -149,10 +372,176 @@ pub(super) fn load_word_kanji_info(words: &[Word]) -> Result<Vec<Kanji>, Error>
         .flatten()
         .map(|i| i.chars().collect::<Vec<_>>())
         .flatten()
-        .filter_map(|i| kanji_resources.by_literal(i).cloned())
-        .unique_by(|i| i.literal)
-        .take(10)
-        .collect::<Vec<_>>();
+        .unique()
+}
 
-    Ok(kanji_literals)
+#[cfg(test)]
+mod test {
+    use super::*;
+    use resources::{models::words::Dict, parse::jmdict::priority::Priority};
+
+    fn word(priorities: Option<Vec<Priority>>) -> Word {
+        Word {
+            reading: resources::models::words::Reading {
+                kana: Dict {
+                    priorities,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_common_filter() {
+        let common = word(Some(vec![Priority::News(1)]));
+        let rare = word(None);
+
+        assert!(common_filter(&common, true));
+        assert!(!common_filter(&rare, true));
+        assert!(common_filter(&rare, false));
+    }
+
+    #[test]
+    fn test_has_kana_reading() {
+        let mut tree = word(None);
+        tree.reading.kana.reading = "き".to_string();
+        tree.reading.kanji = Some(Dict {
+            reading: "木".to_string(),
+            ..Default::default()
+        });
+        let mut kana = word(None);
+        kana.reading.kana.reading = "キ".to_string();
+
+        let reading = kanji::format_reading("き");
+        assert!(has_kana_reading(&tree, &reading, false));
+        assert!(has_kana_reading(&kana, &reading, false));
+        assert!(!has_kana_reading(&kana, "け", false));
+
+        // No cross script matches with strict_script
+        assert!(has_kana_reading(&tree, &reading, true));
+        assert!(!has_kana_reading(&kana, &reading, true));
+        assert!(has_kana_reading(&kana, "キ", true));
+    }
+
+    #[test]
+    fn test_word_kanji_literals_limit() {
+        let mut compound = word(None);
+        compound.reading.kanji = Some(Dict {
+            reading: "国立大学".to_string(),
+            ..Default::default()
+        });
+        let mut university = word(None);
+        university.reading.kanji = Some(Dict {
+            reading: "大学".to_string(),
+            ..Default::default()
+        });
+        let words = vec![compound, university];
+
+        let query = Query {
+            kanji_info_limit: Some(usize::MAX),
+            ..Default::default()
+        };
+        let all = word_kanji_literals(&words)
+            .take(query.get_kanji_info_limit())
+            .collect::<Vec<_>>();
+        assert_eq!(all, vec!['国', '立', '大', '学']);
+
+        let limited = word_kanji_literals(&words).take(2).count();
+        assert_eq!(limited, 2);
+        assert_eq!(Query::default().get_kanji_info_limit(), 10);
+    }
+
+    #[test]
+    fn test_skip_kanji_info() {
+        let mut university = word(None);
+        university.reading.kanji = Some(Dict {
+            reading: "大学".to_string(),
+            ..Default::default()
+        });
+        let words = vec![university];
+
+        let query = Query {
+            kanji_info_limit: Some(5),
+            skip_kanji_info: true,
+            ..Default::default()
+        };
+        assert_eq!(query.get_kanji_info_limit(), 0);
+
+        // Returns before accessing the kanji resources, which aren't loaded within tests
+        let kanji = load_word_kanji_info(&words, query.get_kanji_info_limit()).unwrap();
+        assert!(kanji.is_empty());
+    }
+
+    #[test]
+    fn test_page_after() {
+        let extra = (0..15).collect::<Vec<_>>();
+
+        // 15 compounds with 10 words per page
+        assert!(page_after(&extra, 15, 10, 0, 10).is_empty());
+        assert_eq!(page_after(&extra, 15, 5, 10, 10), &extra[0..5]);
+        assert_eq!(page_after(&extra, 15, 0, 20, 10), &extra[5..15]);
+        assert!(page_after(&extra, 15, 0, 30, 10).is_empty());
+
+        // No compounds at all
+        assert_eq!(page_after(&extra, 0, 0, 0, 10), &extra[0..10]);
+    }
+
+    #[test]
+    fn test_reading_in_segment() {
+        let ratio = MAX_READING_LEN_RATIO;
+
+        // A single mora reading used to match anywhere within long segments
+        assert!("あかさたなは".contains('か'));
+        assert!(!reading_in_segment("あかさたなは", "か", ratio));
+
+        assert!(reading_in_segment("あかさたなは", "あ", ratio));
+        assert!(reading_in_segment("あかさたなは", "なは", ratio));
+        assert!(reading_in_segment("あかさ", "か", ratio));
+        assert!(reading_in_segment("あかさたなは", "か", 6));
+    }
+
+    #[test]
+    fn test_joyo_order() {
+        let compound = |kanji: &str| {
+            let mut compound = word(None);
+            compound.reading.kanji = Some(Dict {
+                reading: kanji.to_string(),
+                ..Default::default()
+            });
+            compound
+        };
+        let is_joyo = |literal: char| !"檸檬".contains(literal);
+
+        let joyo = compound("果物");
+        let rare = compound("檸檬");
+        let score = 50;
+        assert!(joyo_order(score, &joyo, is_joyo) > joyo_order(score, &rare, is_joyo));
+        assert_eq!(joyo_order(score, &joyo, is_joyo), score);
+
+        // Kana words aren't demoted
+        assert_eq!(joyo_order(score, &word(None), is_joyo), score);
+    }
+
+    #[test]
+    fn test_is_kanji_word() {
+        let mut nama = word(None);
+        nama.reading.kana.reading = "なま".to_string();
+        nama.reading.kanji = Some(Dict {
+            reading: "生".to_string(),
+            ..Default::default()
+        });
+
+        let nama_kun = kanji::format_reading_with_literal('生', "なま", ReadingType::Kunyomi);
+        let sei_on = kanji::format_reading_with_literal('生', "セイ", ReadingType::Onyomi);
+        let iki_kun = kanji::format_reading_with_literal('生', "い.きる", ReadingType::Kunyomi);
+
+        assert!(is_kanji_word(&nama, &nama_kun, "なま", false));
+        assert!(!is_kanji_word(&nama, &sei_on, "せい", false));
+        assert!(!is_kanji_word(&nama, &iki_kun, "いきる", false));
+
+        assert!(is_kanji_word(&nama, &nama_kun, "なま", true));
+        assert!(!is_kanji_word(&nama, &nama_kun, "ナマ", true));
+    }
 }
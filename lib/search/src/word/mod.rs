@@ -1,3 +1,5 @@
+pub mod cache;
+mod did_you_mean;
 mod kanji;
 pub mod order;
 pub mod result;
@@ -12,6 +14,8 @@ use crate::{
         SearchTask,
     },
     query::Form,
+    search_order::SearchOrder,
+    slow_query, SearchMode,
 };
 
 use self::result::{InflectionInformation, WordResult};
@@ -21,8 +25,8 @@ use itertools::Itertools;
 use japanese::{inflection::SentencePart, JapaneseExt};
 use resources::{
     models::{
-        kanji::Kanji,
-        words::{filter_languages, Word},
+        kanji::{self as kanji_model, Kanji, ReadingType},
+        words::{filter_languages_fallback, filter_languages_with, Word},
     },
     parse::jmdict::{languages::Language, part_of_speech::PosSimple},
 };
@@ -35,13 +39,163 @@ pub(self) struct Search<'a> {
     query: &'a Query,
 }
 
-/// Search among all data based on the input query
+/// Search among all data based on the input query. Finding nothing is not an error: an `Ok` value
+/// with an empty result is returned instead. Errors are only returned if searching itself failed
 #[inline]
 pub fn search(query: &Query) -> Result<WordResult, Error> {
+    if query.only_symbols {
+        return Ok(WordResult::default());
+    }
+
     Ok(Search { query }.do_search()?)
 }
 
-#[derive(Default)]
+/// Returns the word with the sequence id `seq` with only the senses of `language` (and english if
+/// `show_english` is set). Returns `None` if no such word exists
+pub fn by_seq(
+    seq: u32,
+    language: Language,
+    show_english: bool,
+    language_fallback: bool,
+) -> Option<Word> {
+    let mut word = resources::get().words().by_sequence(seq)?.clone();
+    let word_iter = std::iter::once(&mut word);
+    filter_seq_languages(word_iter, language, show_english, language_fallback);
+    Some(word)
+}
+
+/// Returns the words with the sequence ids `seqs` in the same order, like [`by_seq`]. Unknown
+/// sequence ids are skipped
+pub fn by_seqs(
+    seqs: &[u32],
+    language: Language,
+    show_english: bool,
+    language_fallback: bool,
+) -> Vec<Word> {
+    let word_storage = resources::get().words();
+    let mut words = words_by_seqs(seqs, |seq| word_storage.by_sequence(seq));
+
+    filter_seq_languages(words.iter_mut(), language, show_english, language_fallback);
+
+    words
+}
+
+/// Returns true if `word` has a reading matching `query` after normalization which is written in
+/// the same kana script as `query`
+fn has_reading_in_script(word: &Word, query: &str) -> bool {
+    let normalized = japanese::normalize_reading(query);
+    let script = kana_script(query);
+    word.reading_iter(true).any(|reading| {
+        kana_script(&reading.reading) == script
+            && japanese::normalize_reading(&reading.reading) == normalized
+    })
+}
+
+/// Returns whether `text` contains hiragana and whether it contains katakana. The long vowel
+/// mark ー is used in both scripts and therefore ignored
+fn kana_script(text: &str) -> (bool, bool) {
+    let kana = text.chars().filter(|c| *c != 'ー');
+    kana.fold((false, false), |(hira, kata), c| {
+        (hira || c.is_hiragana(), kata || c.is_katakana())
+    })
+}
+
+/// Returns `reading` without its trailing ー, or with one if it ends with katakana
+fn long_mark_variant(reading: &str) -> Option<String> {
+    if !reading.is_kana() {
+        return None;
+    }
+
+    let stripped = japanese::strip_trailing_long_mark(reading);
+    if stripped != reading {
+        return (!stripped.is_empty()).then(|| stripped.to_string());
+    }
+
+    let ends_with_katakana = reading.chars().last().map_or(false, |i| i.is_katakana());
+    ends_with_katakana.then(|| format!("{}ー", reading))
+}
+
+/// Returns true if a reading of `word` matches the affix `query` in `mode`, eg. 愛する for 〜する
+fn affix_filter(word: &Word, query: &str, mode: SearchMode) -> bool {
+    word.reading_iter(true)
+        .any(|i| i.reading != query && mode.str_eq(i.reading.as_str(), query, false))
+}
+
+/// Returns the alternate spellings of `query_str` to search for, if enabled by `query`
+fn spelling_variants(query: &Query, query_str: &str) -> Vec<&'static str> {
+    if !query.expand_variants {
+        return vec![];
+    }
+
+    japanese::variants::spelling_variants(query_str).collect()
+}
+
+/// Returns true if `word` has a kanji reading which is written like `form` with different
+/// okurigana and read like `form` is read in one of `readings`
+fn has_okurigana_variant(word: &Word, form: &str, readings: &[String]) -> bool {
+    let kana = word.reading_iter(true).filter(|i| !i.kanji).collect_vec();
+
+    word.reading_iter(false).filter(|i| i.kanji).any(|written| {
+        kana.iter()
+            .filter(|i| i.applies_to(&written.reading))
+            .any(|i| {
+                readings.iter().any(|reading| {
+                    japanese::is_okurigana_variant(&written.reading, &i.reading, form, reading)
+                })
+            })
+    })
+}
+
+/// Returns the kana readings of `form`: those of `words` written as `form` and those of the kun
+/// readings of `kanji` which are written as `form`, eg. のぼる for 上る
+fn form_readings(form: &str, kanji: &Kanji, words: &[&Word]) -> Vec<String> {
+    let word_readings = words.iter().flat_map(|word| {
+        let written = word
+            .reading_iter(false)
+            .any(|i| i.kanji && i.reading == form);
+        word.reading_iter(true)
+            .filter(move |i| written && !i.kanji && i.applies_to(form))
+            .map(|i| i.reading.clone())
+    });
+
+    let kun_readings = kanji
+        .kunyomi
+        .iter()
+        .flatten()
+        .filter(|i| {
+            kanji_model::format_reading_with_literal(kanji.literal, i, ReadingType::Kunyomi) == form
+        })
+        .map(|i| kanji_model::format_reading(i.as_str()));
+
+    word_readings.chain(kun_readings).unique().collect()
+}
+
+/// Returns the words of `seqs` found by `lookup`, in the order of `seqs`
+fn words_by_seqs<'a, F>(seqs: &[u32], lookup: F) -> Vec<Word>
+where
+    F: Fn(u32) -> Option<&'a Word>,
+{
+    seqs.iter()
+        .filter_map(|seq| lookup(*seq))
+        .cloned()
+        .collect()
+}
+
+/// Filters the senses of words loaded by their sequence ids
+fn filter_seq_languages<'a, I: 'a + Iterator<Item = &'a mut Word>>(
+    words: I,
+    language: Language,
+    show_english: bool,
+    language_fallback: bool,
+) {
+    if language_fallback {
+        filter_languages_fallback(words, language, show_english, false);
+    } else {
+        filter_languages_with(words, language, show_english, false);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
 pub(crate) struct ResultData {
     pub(crate) words: Vec<Word>,
     pub(crate) infl_info: Option<InflectionInformation>,
@@ -49,21 +203,43 @@ pub(crate) struct ResultData {
     pub(crate) sentence_index: i32,
     pub(crate) sentence_parts: Option<Vec<SentencePart>>,
     pub(crate) searched_query: String,
+    pub(crate) used_fallback: bool,
 }
 
 impl<'a> Search<'a> {
     /// Do the search
     fn do_search(&self) -> Result<WordResult, Error> {
         let start = Instant::now();
+        let query_str = &self.query.query;
+        let count = |res: &Result<ResultData, Error>| res.as_ref().map(|i| i.count).unwrap_or(0);
         let search_result = match self.query.form {
-            Form::KanjiReading(_) => kanji::by_reading(self)?,
+            Form::KanjiReading(_) => slow_query::measure(
+                "kanji reading",
+                query_str,
+                || kanji::by_reading(self),
+                count,
+            )?,
             Form::TagOnly => tag_only::search(self)?,
-            _ => self.do_word_search()?,
+            _ => slow_query::measure("word", query_str, || self.do_word_search(), count)?,
         };
 
-        let words = search_result.words;
+        let mut words = search_result.words;
+        if self.query.merge_senses {
+            for word in words.iter_mut() {
+                word.merge_duplicate_senses();
+            }
+        }
+
+        if let Some(order) = self.query.order {
+            SearchOrder::new(self.query, &None).sort_words(&mut words, order);
+        }
 
-        let kanji_results = kanji::load_word_kanji_info(&words)?;
+        // Only look for a suggestion if nothing was found
+        let suggestion = (search_result.count == 0 && !self.query.form.is_tag_only())
+            .then(|| did_you_mean::suggest(&self.query.query))
+            .flatten();
+
+        let kanji_results = kanji::load_word_kanji_info(&words, self.query.get_kanji_info_limit())?;
 
         let res = WordResult {
             contains_kanji: kanji_results.len() > 0,
@@ -73,6 +249,8 @@ impl<'a> Search<'a> {
             sentence_parts: search_result.sentence_parts,
             sentence_index: search_result.sentence_index,
             searched_query: search_result.searched_query,
+            used_fallback: search_result.used_fallback,
+            suggestion,
         };
         println!("search took: {:?}", start.elapsed());
         Ok(res)
@@ -80,7 +258,12 @@ impl<'a> Search<'a> {
 
     /// Search by a word
     fn do_word_search(&self) -> Result<ResultData, Error> {
-        let native_word_res = self.native_results(&self.query.query)?;
+        let mut native_word_res = self.native_results(&self.query.query)?;
+        if native_word_res.count == 0 && self.query.auto_deinflect {
+            if let Some(deinflected) = self.deinflected_results()? {
+                native_word_res = deinflected;
+            }
+        }
         let gloss_word_res = self.gloss_results()?;
 
         let sentence_parts = native_word_res
@@ -89,17 +272,23 @@ impl<'a> Search<'a> {
             .unwrap_or(gloss_word_res.sentence_parts);
 
         // Chain native and word results into one vector
+        let words = native_word_res
+            .words
+            .into_iter()
+            .chain(gloss_word_res.words)
+            .collect_vec();
+        let word_count = words.len();
+        let words = dedup_words(words);
+        let duplicates = word_count - words.len();
+
         Ok(ResultData {
-            words: native_word_res
-                .words
-                .into_iter()
-                .chain(gloss_word_res.words)
-                .collect_vec(),
+            words,
             infl_info: native_word_res.infl_info,
-            count: native_word_res.count + gloss_word_res.count,
+            count: (native_word_res.count + gloss_word_res.count).saturating_sub(duplicates),
             sentence_parts,
             sentence_index: self.query.word_index as i32,
             searched_query: native_word_res.searched_query,
+            used_fallback: native_word_res.used_fallback,
         })
     }
 
@@ -188,10 +377,27 @@ impl<'a> Search<'a> {
             .offset(self.query.page_offset)
             .threshold(0.04f32);
 
+        // Single characters are contained in a huge amount of words. Only keep short ones
+        let single_char = is_single_char(query).then(|| query.to_string());
+
         // apply user filter
         let q_cloned = self.query.clone();
         let pos_filter = self.get_pos_filter(sentence);
-        search_task.set_result_filter(move |word| Self::word_filter(&q_cloned, word, &pos_filter));
+        search_task.set_result_filter(move |word| {
+            if let Some(ref single_char) = single_char {
+                if !single_char_filter(word, single_char) {
+                    return false;
+                }
+            }
+
+            if let Some(mode) = q_cloned.affix_mode {
+                if !affix_filter(word, &q_cloned.query, mode) {
+                    return false;
+                }
+            }
+
+            Self::word_filter(&q_cloned, word, &pos_filter)
+        });
 
         // Set order function;
         let original_query = original_query.to_string();
@@ -209,6 +415,10 @@ impl<'a> Search<'a> {
         }
 
         let (query, morpheme, sentence) = self.get_query(query_str)?;
+        let expanded = self
+            .query
+            .expand_iteration_marks
+            .then(|| japanese::expand_iteration_marks(&query));
 
         let mut search_task =
             self.native_search_task(&query, &self.query.query, sentence.is_some());
@@ -218,16 +428,92 @@ impl<'a> Search<'a> {
             search_task.add_query(&self.query.query);
         }
 
+        // Search for the query without iteration marks too (eg. 時々 as 時時)
+        if let Some(expanded) = expanded.as_ref().filter(|i| **i != query) {
+            search_task.add_query(expanded);
+        }
+
+        // Search for alternate spellings too (eg. 子ども for 子供)
+        let variants = spelling_variants(self.query, &query);
+        for &variant in variants.iter() {
+            search_task.add_query(variant);
+        }
+
         let res = search_task.find()?;
-        let count = res.len();
+        let mut count = res.len();
 
         let mut wordresults = res.item_iter().cloned().collect::<Vec<_>>();
 
-        filter_languages(
+        // Results only found through an alternate spelling
+        let mut used_fallback = !variants.is_empty()
+            && !wordresults
+                .iter()
+                .any(|word| word.reading_iter(true).any(|i| i.reading == query));
+
+        // Words written with different okurigana are alternate spellings as well. They're listed
+        // after the words found by the search engine, which already contains words read as `query`
+        if self.query.expand_variants && count > 0 && query.has_kanji() {
+            let variants = self
+                .okurigana_variant_results(&query, sentence.is_some())
+                .into_iter()
+                .filter(|word| !word.reading_iter(true).any(|i| i.reading == query))
+                .collect_vec();
+            let page = kanji::page_after(
+                &variants,
+                count,
+                wordresults.len(),
+                self.query.page_offset,
+                self.query.settings.page_size as usize,
+            );
+
+            wordresults.extend(page.iter().map(|i| (*i).clone()));
+            count += variants.len();
+        }
+
+        // Search for words with the same normalized reading (eg. ラーメン and らあめん)
+        if wordresults.is_empty() && self.query.normalize_kana && query_str.is_kana() {
+            wordresults = self.normalized_reading_results(query_str, sentence.is_some());
+            count = wordresults.len();
+            used_fallback = count > 0;
+        }
+
+        // Loanwords are spelled with and without a trailing long mark (eg. コンピュータ)
+        if wordresults.is_empty() && self.query.ignore_trailing_long_mark {
+            if let Some(variant) = long_mark_variant(query_str) {
+                wordresults = self.normalized_reading_results(&variant, sentence.is_some());
+                count = wordresults.len();
+                used_fallback = count > 0;
+            }
+        }
+
+        // Search for words written with different okurigana (eg. 表す and 表わす)
+        if wordresults.is_empty() && query_str.has_kanji() && query_str.has_kana() {
+            let variants = self.okurigana_variant_results(query_str, sentence.is_some());
+            let page = kanji::page_after(
+                &variants,
+                0,
+                0,
+                self.query.page_offset,
+                self.query.settings.page_size as usize,
+            );
+
+            wordresults = page.iter().map(|i| (*i).clone()).collect();
+            count = variants.len();
+            used_fallback = count > 0;
+        }
+
+        filter_languages_with(
             wordresults.iter_mut(),
             self.query.settings.user_lang,
             self.query.settings.show_english,
+            self.query.settings.prefer_monolingual,
         );
+        self.retain_fields(&mut wordresults);
+
+        // Hide senses which don't apply to the searched form
+        for word in wordresults.iter_mut() {
+            word.retain_form_senses(&query);
+        }
 
         let infl_info = inflection_info(&morpheme);
 
@@ -242,20 +528,100 @@ impl<'a> Search<'a> {
             sentence_parts: sentence,
             sentence_index: self.query.word_index as i32,
             searched_query,
+            used_fallback,
         })
     }
 
+    /// Returns words having a reading equal to `query_str` once both are normalized. With
+    /// `strict_script` set, the matching reading has to be written in the same kana script
+    fn normalized_reading_results(&self, query_str: &str, sentence: bool) -> Vec<Word> {
+        let pos_filter = self.get_pos_filter(sentence);
+        let strict_script = self.query.strict_script;
+        resources::get()
+            .words()
+            .by_normalized_reading(query_str)
+            .filter(|word| !strict_script || has_reading_in_script(word, query_str))
+            .filter(|word| Self::word_filter(&self.query, word, &pos_filter))
+            .take(self.query.settings.page_size as usize)
+            .cloned()
+            .collect()
+    }
+
+    /// Returns words having a kanji reading which only differs from `query_str` in its okurigana
+    /// and is read the same. Those are looked up among the compounds of the first kanji of
+    /// `query_str`
+    fn okurigana_variant_results(&self, query_str: &str, sentence: bool) -> Vec<&'static Word> {
+        let kanji = query_str
+            .chars()
+            .find(|i| i.is_kanji() || i.is_rare_kanji())
+            .and_then(|i| resources::get().kanji().by_literal(i));
+
+        let kanji = match kanji {
+            Some(kanji) => kanji,
+            None => return vec![],
+        };
+
+        let word_storage = resources::get().words();
+        let compounds = kanji
+            .kun_dicts
+            .iter()
+            .chain(kanji.on_dicts.iter())
+            .flatten()
+            .unique()
+            .filter_map(|seq| word_storage.by_sequence(*seq))
+            .collect_vec();
+
+        let readings = form_readings(query_str, kanji, &compounds);
+        let pos_filter = self.get_pos_filter(sentence);
+        compounds
+            .into_iter()
+            .filter(|word| has_okurigana_variant(word, query_str, &readings))
+            .filter(|word| Self::word_filter(&self.query, word, &pos_filter))
+            .collect()
+    }
+
+    /// Searches for the first dictionary form of the query (if it is an inflected word) which
+    /// exists. Returns `None` if the query can't be deinflected into any known word
+    fn deinflected_results(&self) -> Result<Option<ResultData>, Error> {
+        if !self.query.query.is_japanese() {
+            return Ok(None);
+        }
+
+        let deinflection = japanese::inflection::deinflect(&self.query.query)
+            .into_iter()
+            .find(|i| SearchTask::<native::Engine>::new(&i.base).has_term());
+
+        let deinflection = match deinflection {
+            Some(d) => d,
+            None => return Ok(None),
+        };
+
+        let mut res = self.native_results(&deinflection.base)?;
+        if res.count == 0 {
+            return Ok(None);
+        }
+
+        res.infl_info = Some(InflectionInformation {
+            lexeme: deinflection.base,
+            forms: deinflection.inflections,
+        });
+        res.searched_query = self.query.query.clone();
+        res.used_fallback = true;
+
+        Ok(Some(res))
+    }
+
     /// Returns a `SearchTask` for the current query. This will be used to find all words for
     /// the search
-    fn gloss_search_task(&self) -> SearchTask<foreign::Engine> {
+    fn gloss_search_task<'b>(&'b self, query_str: &'b str) -> SearchTask<'b, foreign::Engine> {
         let mut search_task: SearchTask<foreign::Engine> =
-            SearchTask::with_language(&self.query.query, self.query.settings.user_lang)
+            SearchTask::with_language(query_str, self.query.settings.user_lang)
                 .limit(self.query.settings.page_size as usize)
                 .offset(self.query.page_offset)
                 .threshold(0.3f32);
 
         if self.query.settings.show_english && self.query.settings.user_lang != Language::English {
-            search_task.add_language_query(&self.query.query, Language::English);
+            search_task.add_language_query(query_str, Language::English);
         }
 
         // Set user defined filter
@@ -281,10 +647,17 @@ impl<'a> Search<'a> {
             return Ok(ResultData::default());
         }
 
-        let search_task = self.gloss_search_task();
+        let search_task = self.gloss_search_task(&self.query.query);
 
         // Do the search
         let mut res = search_task.find()?;
+
+        // Also search for the query without diacritics
+        let unaccented = utils::unaccent(&self.query.query);
+        if unaccented != self.query.query {
+            res.merge(self.gloss_search_task(&unaccented).find()?);
+        }
+
         let count = res.len();
 
         if !self.query.use_original
@@ -298,11 +671,13 @@ impl<'a> Search<'a> {
 
         let mut wordresults = res.item_iter().cloned().collect::<Vec<_>>();
 
-        filter_languages(
+        filter_languages_with(
             wordresults.iter_mut(),
             self.query.settings.user_lang,
             self.query.settings.show_english,
+            self.query.settings.prefer_monolingual,
         );
+        self.retain_fields(&mut wordresults);
 
         Ok(ResultData {
             count,
@@ -330,6 +705,18 @@ impl<'a> Search<'a> {
             .collect_vec()
     }
 
+    /// Trims the senses of `words` to the domains of the queries field tags
+    fn retain_fields(&self, words: &mut [Word]) {
+        let fields = self.query.get_field_tags().copied().collect::<Vec<_>>();
+        if fields.is_empty() {
+            return;
+        }
+
+        for word in words {
+            word.retain_fields(&fields);
+        }
+    }
+
     /// Returns false if a word should be filtered out of results
     fn word_filter(query: &Query, word: &Word, pos_filter: &Option<Vec<PosSimple>>) -> bool {
         // Apply pos tag filter
@@ -345,6 +732,13 @@ impl<'a> Search<'a> {
             return false;
         }
 
+        // Apply detailed pos tag filter
+        for pos_filter in query.get_detailed_part_of_speech_tags() {
+            if !word.has_detailed_pos(*pos_filter) {
+                return false;
+            }
+        }
+
         // Apply misc filter
         for misc_filter in query.get_misc_tags() {
             if !word.has_misc(*misc_filter) {
@@ -352,10 +746,35 @@ impl<'a> Search<'a> {
             }
         }
 
+        // Apply field filter
+        let fields = query.get_field_tags().copied().collect::<Vec<_>>();
+        if !fields.is_empty() && !word.has_field(&fields) {
+            return false;
+        }
+
         true
     }
 }
 
+/// Max length of words returned for queries consisting of a single character
+const SINGLE_CHAR_MAX_LEN: usize = 3;
+
+/// Returns `true` if `query` is a single kana or kanji
+#[inline]
+fn is_single_char(query: &str) -> bool {
+    query.chars().count() == 1 && query.is_japanese()
+}
+
+/// Returns false if `word` should be filtered out of the results of the single character query
+/// `query`. Only words being `query` or short words containing `query` are kept
+fn single_char_filter(word: &Word, query: &str) -> bool {
+    word.reading.iter(true).any(|reading| {
+        let reading = &reading.reading;
+        reading == query
+            || (reading.contains(query) && reading.chars().count() <= SINGLE_CHAR_MAX_LEN)
+    })
+}
+
 /// Returns information about word inflections, if available
 fn inflection_info(morpheme: &Option<WordItem>) -> Option<InflectionInformation> {
     morpheme.as_ref().and_then(|i| {
@@ -408,5 +827,216 @@ fn guess_native(search: Search) -> Option<Guess> {
 }
 
 fn guess_foreign(search: Search) -> Option<Guess> {
-    search.gloss_search_task().estimate_result_count().ok()
+    search
+        .gloss_search_task(&search.query.query)
+        .estimate_result_count()
+        .ok()
+}
+
+/// Removes words representing the same dictionary entry. Only the first (highest ranked)
+/// occurrence of each word is kept
+fn dedup_words(words: Vec<Word>) -> Vec<Word> {
+    let mut seen = std::collections::HashSet::with_capacity(words.len());
+    words
+        .into_iter()
+        .filter(|word| {
+            let (seq, reading) = word.dedup_key();
+            seen.insert((seq, reading.to_string()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use resources::models::words::{Dict, Reading, Sense};
+
+    fn word(sequence: u32, senses: usize) -> Word {
+        Word {
+            sequence,
+            reading: Reading {
+                kana: Dict {
+                    reading: "たべる".to_string(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            senses: vec![Sense::default(); senses],
+            ..Default::default()
+        }
+    }
+
+    fn word_with_kana(kana: &str) -> Word {
+        Word {
+            reading: Reading {
+                kana: Dict {
+                    reading: kana.to_string(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_single_char_filter() {
+        assert!(is_single_char("あ"));
+        assert!(is_single_char("日"));
+        assert!(!is_single_char("ああ"));
+        assert!(!is_single_char("a"));
+
+        assert!(single_char_filter(&word_with_kana("あ"), "あ"));
+        assert!(single_char_filter(&word_with_kana("ああ"), "あ"));
+        assert!(!single_char_filter(&word_with_kana("あいさつ"), "あ"));
+        assert!(!single_char_filter(&word_with_kana("いか"), "あ"));
+    }
+
+    #[test]
+    fn test_dedup_words() {
+        let words = vec![word(1, 2), word(2, 1), word(1, 1)];
+        let words = dedup_words(words);
+
+        assert_eq!(words.len(), 2);
+        assert_eq!(words[0].sequence, 1);
+        assert_eq!(words[0].senses.len(), 2);
+        assert_eq!(words[1].sequence, 2);
+    }
+
+    #[test]
+    fn test_result_data_clone_eq() {
+        let data = ResultData {
+            words: vec![word(1, 2), word(2, 1)],
+            count: 2,
+            searched_query: "たべる".to_string(),
+            ..Default::default()
+        };
+
+        let cloned = data.clone();
+        assert_eq!(cloned, data);
+
+        let other = ResultData {
+            count: 3,
+            ..data.clone()
+        };
+        assert_ne!(other, data);
+    }
+
+    #[test]
+    fn test_words_by_seqs() {
+        let stored = vec![word(1, 1), word(2, 1), word(3, 1)];
+        let lookup = |seq: u32| stored.iter().find(|i| i.sequence == seq);
+
+        let words = words_by_seqs(&[3, 42, 1, 0], lookup);
+        let seqs = words.iter().map(|i| i.sequence).collect::<Vec<_>>();
+        assert_eq!(seqs, vec![3, 1]);
+    }
+
+    #[test]
+    fn test_strict_script() {
+        let coffee = word_with_kana("コーヒー");
+        assert!(has_reading_in_script(&coffee, "コーヒー"));
+        assert!(has_reading_in_script(&coffee, "コオヒイ"));
+        assert!(!has_reading_in_script(&coffee, "こーひー"));
+        assert!(!has_reading_in_script(&coffee, "こおひい"));
+
+        let mother = word_with_kana("はは");
+        assert!(has_reading_in_script(&mother, "はは"));
+        assert!(!has_reading_in_script(&mother, "ハハ"));
+    }
+
+    #[test]
+    fn test_okurigana_variant() {
+        let mut arawasu = word_with_kana("あらわす");
+        arawasu.reading.kanji = Some(Dict {
+            reading: "表す".to_string(),
+            kanji: true,
+            ..Default::default()
+        });
+
+        let readings = vec!["あらわす".to_string()];
+        assert!(has_okurigana_variant(&arawasu, "表わす", &readings));
+        assert!(!has_okurigana_variant(&arawasu, "表す", &readings));
+        assert!(!has_okurigana_variant(&arawasu, "現わす", &readings));
+        let other_reading = vec!["ひょうわす".to_string()];
+        assert!(!has_okurigana_variant(&arawasu, "表わす", &other_reading));
+
+        let kana_only = word_with_kana("あらわす");
+        assert!(!has_okurigana_variant(&kana_only, "表わす", &readings));
+    }
+
+    #[test]
+    fn test_okurigana_variant_reading() {
+        let written = |kanji: &str, kana: &str| {
+            let mut word = word_with_kana(kana);
+            word.reading.kanji = Some(Dict {
+                reading: kanji.to_string(),
+                kanji: true,
+                ..Default::default()
+            });
+            word
+        };
+        let hairu = written("入る", "はいる");
+        let ireru = written("入れる", "いれる");
+        let agaru = written("上がる", "あがる");
+
+        let iru = Kanji {
+            literal: '入',
+            kunyomi: Some(vec![
+                "い.る".to_string(),
+                "い.れる".to_string(),
+                "はい.る".to_string(),
+            ]),
+            ..Default::default()
+        };
+        let readings = form_readings("入る", &iru, &[&hairu, &ireru]);
+        assert_eq!(readings, vec!["はいる", "いる"]);
+        assert!(!has_okurigana_variant(&ireru, "入る", &readings));
+
+        let ue = Kanji {
+            literal: '上',
+            kunyomi: Some(vec!["あ.がる".to_string(), "のぼ.る".to_string()]),
+            ..Default::default()
+        };
+        let readings = form_readings("上る", &ue, &[&agaru]);
+        assert_eq!(readings, vec!["のぼる"]);
+        assert!(!has_okurigana_variant(&agaru, "上る", &readings));
+    }
+
+    #[test]
+    fn test_affix_filter() {
+        let suffix = SearchMode::EndsWith;
+        assert!(affix_filter(&word_with_kana("あいする"), "する", suffix));
+        assert!(!affix_filter(&word_with_kana("する"), "する", suffix));
+        assert!(!affix_filter(&word_with_kana("すること"), "する", suffix));
+
+        let prefix = SearchMode::StartsWith;
+        assert!(affix_filter(&word_with_kana("おちゃ"), "お", prefix));
+        assert!(!affix_filter(&word_with_kana("ちゃ"), "お", prefix));
+    }
+
+    #[test]
+    fn test_spelling_variants() {
+        let mut query = Query::default();
+        assert!(spelling_variants(&query, "子供").is_empty());
+
+        query.expand_variants = true;
+        assert!(spelling_variants(&query, "子供").contains(&"子ども"));
+        assert!(spelling_variants(&query, "食べる").is_empty());
+    }
+
+    #[test]
+    fn test_long_mark_variant() {
+        assert_eq!(
+            long_mark_variant("コンピュータ").as_deref(),
+            Some("コンピューター")
+        );
+        assert_eq!(
+            long_mark_variant("コンピューター").as_deref(),
+            Some("コンピュータ")
+        );
+        assert_eq!(long_mark_variant("たべる"), None);
+        assert_eq!(long_mark_variant("ー"), None);
+        assert_eq!(long_mark_variant("computer"), None);
+    }
 }
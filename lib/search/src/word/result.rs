@@ -1,7 +1,11 @@
-use japanese::inflection::{Inflection, SentencePart};
+use japanese::{
+    inflection::{self, Inflection, SentencePart},
+    JapaneseExt,
+};
+use localization::language::Language;
 use resources::models::{kanji::Kanji, words::Word};
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Default)]
 pub struct WordResult {
     pub items: Vec<Item>,
     pub count: usize,
@@ -10,6 +14,11 @@ pub struct WordResult {
     pub sentence_parts: Option<Vec<SentencePart>>,
     pub sentence_index: i32,
     pub searched_query: String,
+    /// Whether the items were found by a fallback (eg. deinflection or an alternate spelling)
+    /// instead of the query itself
+    pub used_fallback: bool,
+    /// A similar query which might yield results. Only set if nothing was found
+    pub suggestion: Option<String>,
 }
 
 impl WordResult {
@@ -17,6 +26,37 @@ impl WordResult {
     pub fn has_word(&self) -> bool {
         self.items.iter().any(|i| i.is_word())
     }
+
+    /// Returns an iterator over all words of the result
+    #[inline]
+    pub fn words(&self) -> impl Iterator<Item = &Word> {
+        self.items.iter().filter_map(|i| match i {
+            Item::Word(word) => Some(word),
+            _ => None,
+        })
+    }
+
+    /// Groups the words into words written with a single kanji and kanji compounds. Useful to
+    /// display results of kanji reading searches. Words without kanji are counted as standalone
+    pub fn group_by_compounds(&self) -> ReadingGroups<'_> {
+        let (compounds, standalone) = self
+            .words()
+            .partition(|word| word.get_reading().reading.kanji_count() > 1);
+
+        ReadingGroups {
+            standalone,
+            compounds,
+        }
+    }
+}
+
+/// Words of a result grouped by the amount of kanji they're written with
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReadingGroups<'a> {
+    /// Words written with a single kanji (or none at all)
+    pub standalone: Vec<&'a Word>,
+    /// Words written with multiple kanji
+    pub compounds: Vec<&'a Word>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -25,6 +65,14 @@ pub struct InflectionInformation {
     pub forms: Vec<Inflection>,
 }
 
+impl InflectionInformation {
+    /// Returns a human readable description of the inflections in `language`
+    #[inline]
+    pub fn explain(&self, language: Language) -> String {
+        inflection::explain(&self.forms, language)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum Item {
     Word(Word),
@@ -66,3 +114,49 @@ pub fn selected(curr: i32, selected: i32) -> &'static str {
         ""
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use resources::models::words::{Dict, Reading};
+
+    fn word(sequence: u32, kanji: &str) -> Word {
+        Word {
+            sequence,
+            reading: Reading {
+                kanji: Some(Dict {
+                    reading: kanji.to_string(),
+                    kanji: true,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_group_by_compounds() {
+        let result = WordResult {
+            items: vec![
+                word(1, "木材").into(),
+                word(2, "木").into(),
+                word(3, "木の実").into(),
+            ],
+            count: 3,
+            contains_kanji: false,
+            inflection_info: None,
+            sentence_parts: None,
+            sentence_index: 0,
+            searched_query: String::new(),
+            used_fallback: false,
+            suggestion: None,
+        };
+
+        let groups = result.group_by_compounds();
+        let sequences = |words: &[&Word]| words.iter().map(|i| i.sequence).collect::<Vec<_>>();
+        assert_eq!(sequences(&groups.standalone), vec![2]);
+        assert_eq!(sequences(&groups.compounds), vec![1, 3]);
+        assert_eq!(result.words().count(), 3);
+    }
+}
@@ -1,8 +1,11 @@
 use error::Error;
-use resources::models::words::filter_languages;
+use resources::{
+    models::words::{filter_languages_with, Word},
+    parse::jmdict::part_of_speech::PosSimple,
+};
 use utils::to_option;
 
-use crate::query::Tag;
+use crate::{query::Tag, search_order::frequency_score};
 
 use super::{ResultData, Search};
 
@@ -36,10 +39,11 @@ fn jlpt_search(search: &Search<'_>, jlpt: u8) -> Result<ResultData, Error> {
         .cloned()
         .collect::<Vec<_>>();
 
-    filter_languages(
+    filter_languages_with(
         wordresults.iter_mut(),
         search.query.settings.user_lang,
         search.query.settings.show_english,
+        search.query.settings.prefer_monolingual,
     );
 
     wordresults.sort_by(|a, b| a.get_reading().reading.cmp(&b.get_reading().reading));
@@ -58,3 +62,102 @@ fn jlpt_search(search: &Search<'_>, jlpt: u8) -> Result<ResultData, Error> {
         ..Default::default()
     })
 }
+
+/// Returns `limit` words of the given `jlpt` level, optionally restricted to words having the part
+/// of speech `pos`. Words are ordered by their frequency, most frequent first
+pub fn study_list(jlpt: u8, pos: Option<PosSimple>, limit: usize, offset: usize) -> Vec<Word> {
+    match resources::get().word_jlpt(jlpt) {
+        Some(words) => order_study_list(words, pos, limit, offset),
+        None => vec![],
+    }
+}
+
+/// Filters `words` by `pos` and returns the requested page ordered by frequency
+fn order_study_list<'a, I>(
+    words: I,
+    pos: Option<PosSimple>,
+    limit: usize,
+    offset: usize,
+) -> Vec<Word>
+where
+    I: Iterator<Item = &'a Word>,
+{
+    let pos_filter = pos.map(|i| vec![i]);
+
+    let mut words = words
+        .filter(|word| {
+            pos_filter
+                .as_ref()
+                .map(|filter| word.has_pos(filter))
+                .unwrap_or(true)
+        })
+        .collect::<Vec<_>>();
+
+    words.sort_by(|a, b| {
+        frequency_score(b)
+            .cmp(&frequency_score(a))
+            .then(a.sequence.cmp(&b.sequence))
+    });
+
+    words
+        .into_iter()
+        .skip(offset)
+        .take(limit)
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use resources::{
+        models::words::{Dict, Reading, Sense},
+        parse::jmdict::{
+            part_of_speech::{NounType, PartOfSpeech, VerbType},
+            priority::Priority,
+        },
+    };
+
+    fn word(sequence: u32, pos: PartOfSpeech, priorities: Option<Vec<Priority>>) -> Word {
+        Word {
+            sequence,
+            reading: Reading {
+                kana: Dict {
+                    priorities,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            senses: vec![Sense {
+                part_of_speech: vec![pos],
+                ..Default::default()
+            }],
+            jlpt_lvl: Some(5),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_n5_verb_study_list() {
+        let verb = PartOfSpeech::Verb(VerbType::Transitive);
+        let noun = PartOfSpeech::Noun(NounType::Normal);
+        let words = vec![
+            word(1, verb, None),
+            word(2, noun, Some(vec![Priority::Nf(1)])),
+            word(3, verb, Some(vec![Priority::Nf(30)])),
+            word(4, verb, Some(vec![Priority::Nf(2)])),
+            word(5, verb, Some(vec![Priority::News(1)])),
+        ];
+
+        let sequences = |list: Vec<Word>| list.iter().map(|i| i.sequence).collect::<Vec<_>>();
+
+        let list = order_study_list(words.iter(), Some(PosSimple::Verb), 10, 0);
+        assert_eq!(sequences(list), vec![4, 3, 5, 1]);
+
+        let list = order_study_list(words.iter(), Some(PosSimple::Verb), 2, 1);
+        assert_eq!(sequences(list), vec![3, 5]);
+
+        let list = order_study_list(words.iter(), None, 2, 0);
+        assert_eq!(sequences(list), vec![2, 4]);
+    }
+}
@@ -57,16 +57,34 @@ where
         return Some(Ordering::Equal);
     }
 
-    for i in vec {
-        if *i == *a {
-            return Some(Ordering::Less);
-        }
-        if *i == *b {
-            return Some(Ordering::Greater);
-        }
-    }
+    order_by_reference(vec, a, b)
+}
 
-    None
+/// Orders `a` and `b` by their position within `reference`. Elements contained in `reference` are
+/// ordered in front of elements which aren't. Returns `None` if neither `a` nor `b` is in
+/// `reference`
+pub fn order_by_reference<T>(reference: &[T], a: &T, b: &T) -> Option<Ordering>
+where
+    T: PartialEq,
+{
+    order_by_reference_key(reference, a, b, |i| i)
+}
+
+/// Like [`order_by_reference`] but compares the keys returned by `key` against `reference`
+pub fn order_by_reference_key<T, K, F>(reference: &[K], a: &T, b: &T, key: F) -> Option<Ordering>
+where
+    K: PartialEq,
+    F: Fn(&T) -> &K,
+{
+    let a_pos = reference.iter().position(|i| i == key(a));
+    let b_pos = reference.iter().position(|i| i == key(b));
+
+    match (a_pos, b_pos) {
+        (Some(a_pos), Some(b_pos)) => Some(a_pos.cmp(&b_pos)),
+        (Some(_), None) => Some(Ordering::Less),
+        (None, Some(_)) => Some(Ordering::Greater),
+        (None, None) => None,
+    }
 }
 
 /// Returns the real amount of characters in a string
@@ -76,6 +94,31 @@ pub fn real_string_len(s: &str) -> usize {
     s.chars().count()
 }
 
+/// Returns `s` truncated to at most `n` characters. Combining characters (eg. a combining dakuten)
+/// are never separated from the character they belong to
+pub fn truncate_chars(s: &str, n: usize) -> &str {
+    let mut end = s.char_indices().nth(n).map(|i| i.0).unwrap_or(s.len());
+
+    // Move the end in front of the base character of a cut off combining character
+    while end > 0 && s[end..].starts_with(is_combining) {
+        end = s[..end].char_indices().last().map(|i| i.0).unwrap_or(0);
+    }
+
+    &s[..end]
+}
+
+/// Returns the amount of leading characters `a` and `b` have in common
+#[inline]
+pub fn common_prefix_len(a: &str, b: &str) -> usize {
+    a.chars().zip(b.chars()).take_while(|(a, b)| a == b).count()
+}
+
+/// Returns `true` if `c` is a combining character
+#[inline]
+fn is_combining(c: char) -> bool {
+    matches!(c, '\u{0300}'..='\u{036F}' | '\u{3099}' | '\u{309A}')
+}
+
 /// Returns an antisymmetric ordering of [`a`] and [`b`] where `a == true` < `b == true`
 /// Example:
 ///
@@ -247,6 +290,46 @@ pub fn first_letter_upper(s: &str) -> String {
     }
 }
 
+/// Replaces latin characters with diacritics by their base character. Eg. "café" => "cafe"
+pub fn unaccent(s: &str) -> String {
+    s.chars().map(unaccent_char).collect()
+}
+
+/// Returns the base character of a latin character with diacritics or `c` itself
+fn unaccent_char(c: char) -> char {
+    match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => 'a',
+        'À' | 'Á' | 'Â' | 'Ã' | 'Ä' | 'Å' | 'Ā' | 'Ă' | 'Ą' => 'A',
+        'ç' | 'ć' | 'č' => 'c',
+        'Ç' | 'Ć' | 'Č' => 'C',
+        'ď' => 'd',
+        'Ď' => 'D',
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ė' | 'ę' | 'ě' => 'e',
+        'È' | 'É' | 'Ê' | 'Ë' | 'Ē' | 'Ė' | 'Ę' | 'Ě' => 'E',
+        'ì' | 'í' | 'î' | 'ï' | 'ī' | 'į' => 'i',
+        'Ì' | 'Í' | 'Î' | 'Ï' | 'Ī' | 'Į' => 'I',
+        'ł' => 'l',
+        'Ł' => 'L',
+        'ñ' | 'ń' | 'ň' => 'n',
+        'Ñ' | 'Ń' | 'Ň' => 'N',
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ő' => 'o',
+        'Ò' | 'Ó' | 'Ô' | 'Õ' | 'Ö' | 'Ø' | 'Ō' | 'Ő' => 'O',
+        'ř' => 'r',
+        'Ř' => 'R',
+        'ś' | 'š' => 's',
+        'Ś' | 'Š' => 'S',
+        'ť' => 't',
+        'Ť' => 'T',
+        'ù' | 'ú' | 'û' | 'ü' | 'ū' | 'ů' | 'ű' => 'u',
+        'Ù' | 'Ú' | 'Û' | 'Ü' | 'Ū' | 'Ů' | 'Ű' => 'U',
+        'ý' | 'ÿ' => 'y',
+        'Ý' => 'Y',
+        'ź' | 'ż' | 'ž' => 'z',
+        'Ź' | 'Ż' | 'Ž' => 'Z',
+        _ => c,
+    }
+}
+
 /// Returns a random alpha numeric string with the length of [`len`]
 #[inline]
 pub fn rand_alpha_numeric(len: usize) -> String {
@@ -266,3 +349,78 @@ pub fn diff<T: Sub<Output = T> + Ord>(a: T, b: T) -> T {
         b - a
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_order_by_reference() {
+        let reference = ["c", "a", "b"];
+        assert_eq!(
+            order_by_reference(&reference, &"a", &"b"),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            order_by_reference(&reference, &"a", &"c"),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(
+            order_by_reference(&reference, &"x", &"b"),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(order_by_reference(&reference, &"x", &"y"), None);
+        assert_eq!(
+            get_item_order(&reference, &"x", &"x"),
+            Some(Ordering::Equal)
+        );
+    }
+
+    #[test]
+    fn test_order_by_reference_key() {
+        let reference = vec!["よむ".to_string(), "とく".to_string()];
+        let mut items = vec![("読", "ドク"), ("読", "とく"), ("読", "よむ")]
+            .into_iter()
+            .map(|(k, r)| (k.to_string(), r.to_string()))
+            .collect::<Vec<_>>();
+
+        items.sort_by(|a, b| {
+            order_by_reference_key(&reference, a, b, |i| &i.1).unwrap_or(Ordering::Equal)
+        });
+
+        let readings = items.iter().map(|i| i.1.as_str()).collect::<Vec<_>>();
+        assert_eq!(readings, vec!["よむ", "とく", "ドク"]);
+    }
+
+    #[test]
+    fn test_truncate_chars() {
+        assert_eq!(truncate_chars("たべもの", 2), "たべ");
+        assert_eq!(truncate_chars("たべもの", 10), "たべもの");
+        assert_eq!(truncate_chars("", 3), "");
+    }
+
+    #[test]
+    fn test_truncate_chars_combining() {
+        // "かが" with a combining dakuten instead of a precomposed 'が'
+        let s = "か\u{304B}\u{3099}";
+        assert_eq!(truncate_chars(s, 2), "か");
+        assert_eq!(truncate_chars(s, 3), s);
+        assert_eq!(truncate_chars("cafe\u{0301}", 4), "caf");
+    }
+
+    #[test]
+    fn test_common_prefix_len() {
+        assert_eq!(common_prefix_len("たべる", "たべた"), 2);
+        assert_eq!(common_prefix_len("たべる", "たべる"), 3);
+        assert_eq!(common_prefix_len("たべる", "たべ"), 2);
+        assert_eq!(common_prefix_len("たべる", "のむ"), 0);
+        assert_eq!(common_prefix_len("", "のむ"), 0);
+    }
+
+    #[test]
+    fn test_unaccent() {
+        assert_eq!(unaccent("café"), "cafe");
+        assert_eq!(unaccent("Über naïve"), "Uber naive");
+        assert_eq!(unaccent("食べる"), "食べる");
+    }
+}